@@ -0,0 +1,194 @@
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305,
+    XNonce
+};
+use hkdf::Hkdf;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{
+    EphemeralSecret,
+    PublicKey,
+    StaticSecret
+};
+use orbit_physical_market::{
+    ShippingEnvelope,
+    SellerEncryptionKey
+};
+
+pub use x25519_dalek;
+
+const KDF_INFO: &[u8] = b"orbit-shipping-v1";
+
+#[derive(Debug, PartialEq)]
+pub enum ShippingError{
+    UnsupportedScheme(u8),
+    TooLong(usize),
+    Decryption
+}
+
+/// seals an address for the recipient (the seller, or the gift recipient) under a fresh ephemeral key
+pub fn seal(recipient: &PublicKey, address: &[u8]) -> Result<ShippingEnvelope, ShippingError>{
+    if address.len() > max_address_len(){
+        return Err(ShippingError::TooLong(address.len()))
+    }
+
+    let ephemeral_secret = EphemeralSecret::random_from_rng(OsRng);
+    let ephemeral_key = PublicKey::from(&ephemeral_secret);
+    let shared = ephemeral_secret.diffie_hellman(recipient);
+
+    let mut nonce = [0u8; 24];
+    OsRng.fill_bytes(&mut nonce);
+
+    let cipher = envelope_cipher(shared.as_bytes(), &ephemeral_key, recipient);
+    let ciphertext = cipher.encrypt(
+        XNonce::from_slice(&nonce),
+        Payload{
+            msg: address,
+            aad: &[ShippingEnvelope::SCHEME_X25519_XCHACHA20POLY1305]
+        }
+    ).expect("address length was checked against the envelope bound");
+
+    Ok(ShippingEnvelope{
+        scheme: ShippingEnvelope::SCHEME_X25519_XCHACHA20POLY1305,
+        ephemeral_key: ephemeral_key.to_bytes(),
+        nonce,
+        ciphertext
+    })
+}
+
+/// seals an address to the key a seller currently publishes. the order records encryption_key.version
+pub fn seal_for_seller(encryption_key: &SellerEncryptionKey, address: &[u8]) -> Result<ShippingEnvelope, ShippingError>{
+    seal(&PublicKey::from(encryption_key.key), address)
+}
+
+/// opens an envelope with the recipients static x25519 secret
+pub fn open(recipient_secret: &StaticSecret, envelope: &ShippingEnvelope) -> Result<Vec<u8>, ShippingError>{
+    if envelope.scheme != ShippingEnvelope::SCHEME_X25519_XCHACHA20POLY1305{
+        return Err(ShippingError::UnsupportedScheme(envelope.scheme))
+    }
+    if envelope.ciphertext.len() > ShippingEnvelope::MAX_CIPHERTEXT_LEN{
+        return Err(ShippingError::TooLong(envelope.ciphertext.len()))
+    }
+
+    let ephemeral_key = PublicKey::from(envelope.ephemeral_key);
+    let shared = recipient_secret.diffie_hellman(&ephemeral_key);
+
+    let cipher = envelope_cipher(shared.as_bytes(), &ephemeral_key, &PublicKey::from(recipient_secret));
+    cipher.decrypt(
+        XNonce::from_slice(&envelope.nonce),
+        Payload{
+            msg: &envelope.ciphertext,
+            aad: &[envelope.scheme]
+        }
+    ).map_err(|_| ShippingError::Decryption)
+}
+
+/// commitment passed to update_shipping. keep the salt, reveal_tracking needs it back
+pub fn tracking_commitment(tracking_number: &[u8], salt: &[u8; 32]) -> [u8; 32]{
+    let mut hasher = Sha256::new();
+    hasher.update(salt);
+    hasher.update(tracking_number);
+    hasher.finalize().into()
+}
+
+pub fn random_salt() -> [u8; 32]{
+    let mut salt = [0u8; 32];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// the largest plaintext address that still fits in an envelope after the 16 byte auth tag
+pub fn max_address_len() -> usize{
+    ShippingEnvelope::MAX_CIPHERTEXT_LEN - 16
+}
+
+fn envelope_cipher(shared: &[u8; 32], ephemeral_key: &PublicKey, recipient: &PublicKey) -> XChaCha20Poly1305{
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral_key.as_bytes());
+    salt[32..].copy_from_slice(recipient.as_bytes());
+
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(KDF_INFO, &mut key)
+        .expect("32 bytes is a valid hkdf output length");
+    XChaCha20Poly1305::new(&key.into())
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    fn recipient() -> (StaticSecret, PublicKey){
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let public = PublicKey::from(&secret);
+        (secret, public)
+    }
+
+    #[test]
+    fn seal_open_round_trip(){
+        let (secret, public) = recipient();
+        let address = b"1 Infinite Loop, Cupertino CA 95014";
+        let envelope = seal(&public, address).unwrap();
+
+        assert!(envelope.is_valid());
+        assert_eq!(open(&secret, &envelope).unwrap(), address.to_vec());
+    }
+
+    #[test]
+    fn seal_for_seller_uses_published_key(){
+        let (secret, public) = recipient();
+        let encryption_key = SellerEncryptionKey{
+            seller: 1,
+            key: public.to_bytes(),
+            version: 1,
+            updated_at: 0
+        };
+        let envelope = seal_for_seller(&encryption_key, b"po box 7").unwrap();
+        assert_eq!(open(&secret, &envelope).unwrap(), b"po box 7".to_vec());
+    }
+
+    #[test]
+    fn open_rejects_other_recipients(){
+        let (_, public) = recipient();
+        let (other_secret, _) = recipient();
+        let envelope = seal(&public, b"somewhere").unwrap();
+        assert_eq!(open(&other_secret, &envelope), Err(ShippingError::Decryption));
+    }
+
+    #[test]
+    fn open_rejects_tampered_envelopes(){
+        let (secret, public) = recipient();
+        let mut envelope = seal(&public, b"somewhere").unwrap();
+        envelope.ciphertext[0] ^= 1;
+        assert_eq!(open(&secret, &envelope), Err(ShippingError::Decryption));
+
+        envelope.ciphertext[0] ^= 1;
+        envelope.scheme = 2;
+        assert_eq!(open(&secret, &envelope), Err(ShippingError::UnsupportedScheme(2)));
+    }
+
+    #[test]
+    fn seal_rejects_oversized_addresses(){
+        let (secret, public) = recipient();
+        let longest = vec![7u8; max_address_len()];
+        let envelope = seal(&public, &longest).unwrap();
+        assert_eq!(envelope.ciphertext.len(), ShippingEnvelope::MAX_CIPHERTEXT_LEN);
+        assert_eq!(open(&secret, &envelope).unwrap(), longest);
+
+        let too_long = vec![7u8; max_address_len() + 1];
+        assert_eq!(seal(&public, &too_long).err(), Some(ShippingError::TooLong(too_long.len())));
+    }
+
+    #[test]
+    fn commitment_is_salt_then_tracking_number(){
+        let salt = [3u8; 32];
+        let mut hasher = Sha256::new();
+        hasher.update([3u8; 32]);
+        hasher.update(b"1Z999AA10123456784");
+        let expected: [u8; 32] = hasher.finalize().into();
+
+        assert_eq!(tracking_commitment(b"1Z999AA10123456784", &salt), expected);
+        assert_ne!(tracking_commitment(b"1Z999AA10123456784", &random_salt()), expected);
+    }
+}
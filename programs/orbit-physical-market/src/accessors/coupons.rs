@@ -0,0 +1,130 @@
+use anchor_lang::{
+    prelude::*,
+    AccountsClose
+};
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    SellerCoupon,
+    CouponDiscount,
+    errors::PhysicalMarketErrors
+};
+
+///////////////////////////////////////////////////////////////////
+/// SELLER COUPONS
+
+#[derive(Accounts)]
+#[instruction(code: [u8; 16])]
+pub struct CreateCoupon<'info>{
+    #[account(
+        init,
+        payer = seller_wallet,
+        space = 150,
+        seeds = [
+            b"seller_coupon",
+            &seller_account.voter_id.to_le_bytes(),
+            &code
+        ],
+        bump
+    )]
+    pub coupon: Account<'info, SellerCoupon>,
+
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CloseCoupon<'info>{
+    #[account(
+        mut,
+        constraint = coupon.seller == seller_account.voter_id
+    )]
+    pub coupon: Account<'info, SellerCoupon>,
+
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+}
+
+pub fn create_coupon(ctx: Context<CreateCoupon>, code: [u8; 16], discount: CouponDiscount, max_uses: u32, expires_at: Option<i64>, product: Option<u64>) -> Result<()>{
+    if let CouponDiscount::Percent{bps} = discount{
+        if bps > 10000{
+            return err!(PhysicalMarketErrors::InvalidCoupon)
+        }
+    };
+
+    ctx.accounts.coupon.seller = ctx.accounts.seller_account.voter_id;
+    ctx.accounts.coupon.code = code;
+    ctx.accounts.coupon.discount = discount;
+    ctx.accounts.coupon.max_uses = max_uses;
+    ctx.accounts.coupon.uses = 0;
+    ctx.accounts.coupon.expires_at = expires_at;
+    ctx.accounts.coupon.product = product;
+    Ok(())
+}
+
+pub fn close_coupon(ctx: Context<CloseCoupon>) -> Result<()>{
+    ctx.accounts.coupon.close(ctx.accounts.seller_wallet.to_account_info())
+}
+
+/// checks the coupon against the order and uses it up. returns how much comes off the price
+/// product_price is the primary product line, product scoped coupons only discount that
+pub fn redeem_coupon(coupon: &mut SellerCoupon, seller: u64, currency: Pubkey, product: u64, product_price: u64, price: u64) -> Result<u64>{
+    if (coupon.seller != seller) || (coupon.uses >= coupon.max_uses){
+        return err!(PhysicalMarketErrors::InvalidCoupon)
+    };
+    if let Some(expires_at) = coupon.expires_at{
+        if Clock::get()?.unix_timestamp >= expires_at{
+            return err!(PhysicalMarketErrors::InvalidCoupon)
+        }
+    };
+
+    let discountable = match coupon.product{
+        Some(scope) if scope == product => product_price,
+        Some(_) => return err!(PhysicalMarketErrors::InvalidCoupon),
+        None => price
+    };
+
+    let discount = match coupon.discount{
+        CouponDiscount::Percent{bps} => discountable * (bps as u64) / 10000,
+        CouponDiscount::Flat{amount, currency: coupon_currency} => {
+            if coupon_currency != currency{
+                return err!(PhysicalMarketErrors::InvalidCoupon)
+            };
+            amount.min(discountable)
+        }
+    };
+
+    coupon.uses += 1;
+    Ok(discount)
+}
+
+/// hands the use back when the order is refunded in full. the redeemed coupon has to be passed
+/// whenever there was one, the restore is only skipped once the seller really closed it
+pub fn restore_coupon_use(coupon: &Option<UncheckedAccount>, redeemed: Option<Pubkey>) -> Result<()>{
+    let redeemed = match redeemed{
+        Some(redeemed) => redeemed,
+        None => return Ok(())
+    };
+    let coupon = match coupon{
+        Some(coupon) if coupon.key() == redeemed => coupon,
+        _ => return err!(PhysicalMarketErrors::MissingCoupon)
+    };
+    if (coupon.owner != &crate::ID) || coupon.data_is_empty(){
+        return Ok(())
+    };
+
+    let mut coupon = Account::<SellerCoupon>::try_from(coupon)?;
+    coupon.uses = coupon.uses.saturating_sub(1);
+    coupon.exit(&crate::ID)
+}
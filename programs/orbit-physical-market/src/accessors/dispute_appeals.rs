@@ -0,0 +1,573 @@
+use anchor_lang::{
+    prelude::*,
+    AccountsClose,
+    solana_program::{
+        system_instruction::transfer,
+        program::invoke,
+    },
+};
+use anchor_spl::token::{
+    TokenAccount,
+    Mint,
+    Token
+};
+use market_accounts::OrbitMarketAccount;
+use orbit_transaction::TransactionState;
+use orbit_dispute::{
+    program::Dispute,
+    DisputeState,
+    OrbitDispute,
+    cpi::accounts::OpenDispute
+};
+use crate::{
+    PhysicalTransaction,
+    PhysicalMarketConfig,
+    DisputeBond,
+    errors::PhysicalMarketErrors,
+    close_dispute_helper,
+    dispute_bond_amount,
+    counterparty,
+    opener_won,
+    opener_won_split,
+    appeal_window_open,
+    resolution_buyer_bps,
+    discard_dispute_resolution,
+
+    program::OrbitPhysicalMarket
+};
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// DISPUTE APPEALS
+/// once the favored party records the ruling the loser has appeal_window seconds to post an
+/// appeal bond. only the winner can start the clock, they cant settle until its run out.
+/// the loser is whoever the split leaves with half or less, same as for bonds.
+/// an appeal closes the ruled dispute and any split set for it, and opens a fresh one at
+/// appeal_threshold. one appeal per tx
+
+#[derive(Accounts)]
+pub struct RecordDisputeRuling<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen,
+        constraint = physical_transaction.ruling_at == 0
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        constraint = phys_dispute.dispute_transaction == physical_transaction.key(),
+        constraint = phys_dispute.dispute_state == DisputeState::Resolved
+    )]
+    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+
+    #[account(
+        constraint = favor_market_account.voter_id == phys_dispute.favor
+    )]
+    pub favor_market_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        address = favor_market_account.wallet
+    )]
+    pub favor_wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AppealDisputeSol<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen,
+        constraint = physical_transaction.ruling_at != 0,
+        constraint = !physical_transaction.appealed,
+        constraint = physical_transaction.metadata.currency == System::id()
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_account",
+            physical_transaction.key().as_ref()
+        ],
+        seeds::program = orbit_dispute::ID,
+        bump,
+        constraint = phys_dispute.dispute_state == DisputeState::Resolved,
+        has_one = funder
+    )]
+    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+
+    /// CHECK: read through resolution_buyer_bps and discarded by discard_dispute_resolution, may not be initialized
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_resolution",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub dispute_resolution: UncheckedAccount<'info>,
+
+    /// whoever paid for the split, required when there is one
+    #[account(mut)]
+    pub resolution_payer: Option<SystemAccount<'info>>,
+
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = appellant_wallet,
+        space = 100,
+        seeds = [
+            b"appeal_bond",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub appeal_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        constraint = !opener_won_split(
+            appellant_account.voter_id == physical_transaction.metadata.buyer,
+            resolution_buyer_bps(&dispute_resolution, phys_dispute.favor == physical_transaction.metadata.buyer)
+        ),
+        constraint =
+        (appellant_account.voter_id == physical_transaction.metadata.buyer) ||
+        (appellant_account.voter_id == physical_transaction.metadata.seller)
+    )]
+    pub appellant_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = appellant_account.wallet
+    )]
+    pub appellant_wallet: Signer<'info>,
+
+    #[account(
+        constraint = buyer.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        constraint = seller.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub dispute_program: Program<'info, Dispute>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct AppealDisputeSpl<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen,
+        constraint = physical_transaction.ruling_at != 0,
+        constraint = !physical_transaction.appealed
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_account",
+            physical_transaction.key().as_ref()
+        ],
+        seeds::program = orbit_dispute::ID,
+        bump,
+        constraint = phys_dispute.dispute_state == DisputeState::Resolved,
+        has_one = funder
+    )]
+    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+
+    /// CHECK: read through resolution_buyer_bps and discarded by discard_dispute_resolution, may not be initialized
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_resolution",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub dispute_resolution: UncheckedAccount<'info>,
+
+    /// whoever paid for the split, required when there is one
+    #[account(mut)]
+    pub resolution_payer: Option<SystemAccount<'info>>,
+
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = appellant_wallet,
+        space = 100,
+        seeds = [
+            b"appeal_bond",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub appeal_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        init,
+        token::mint = token_mint,
+        token::authority = physical_auth,
+        seeds = [
+            b"appeal_bond_escrow",
+            physical_transaction.key().as_ref()
+        ],
+        bump,
+        payer = appellant_wallet
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        address = physical_transaction.metadata.currency
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        constraint = !opener_won_split(
+            appellant_account.voter_id == physical_transaction.metadata.buyer,
+            resolution_buyer_bps(&dispute_resolution, phys_dispute.favor == physical_transaction.metadata.buyer)
+        ),
+        constraint =
+        (appellant_account.voter_id == physical_transaction.metadata.buyer) ||
+        (appellant_account.voter_id == physical_transaction.metadata.seller)
+    )]
+    pub appellant_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = appellant_account.wallet
+    )]
+    pub appellant_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        token::authority = appellant_wallet
+    )]
+    pub appellant_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = buyer.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        constraint = seller.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub dispute_program: Program<'info, Dispute>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>
+}
+
+#[derive(Accounts)]
+pub struct SettleAppealBondSol<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.dispute_buyer_bps.is_some()
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"appeal_bond",
+            physical_transaction.key().as_ref()
+        ],
+        bump,
+        has_one = opener_wallet
+    )]
+    pub appeal_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(mut)]
+    pub opener_wallet: SystemAccount<'info>,
+
+    #[account(
+        constraint = counterparty_account.voter_id == counterparty(&appeal_bond, &physical_transaction)
+    )]
+    pub counterparty_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = counterparty_account.wallet
+    )]
+    pub counterparty_wallet: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleAppealBondSpl<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.dispute_buyer_bps.is_some()
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"appeal_bond",
+            physical_transaction.key().as_ref()
+        ],
+        bump,
+        has_one = opener_wallet
+    )]
+    pub appeal_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"appeal_bond_escrow",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub opener_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = opener_wallet
+    )]
+    pub opener_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = counterparty_account.voter_id == counterparty(&appeal_bond, &physical_transaction)
+    )]
+    pub counterparty_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        token::authority = counterparty_account.wallet
+    )]
+    pub counterparty_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// closes the ruled dispute and opens the appeal in its place
+pub fn escalate_dispute_helper<'a>(dispute_program: AccountInfo<'a>, dispute: AccountInfo<'a>, funder: AccountInfo<'a>, physical_transaction: AccountInfo<'a>, buyer: AccountInfo<'a>, seller: AccountInfo<'a>, payer: AccountInfo<'a>, program_auth: AccountInfo<'a>, program: AccountInfo<'a>, system_program: AccountInfo<'a>, seeds: &[&[&[u8]]], threshold: u8) -> Result<()>{
+    close_dispute_helper(
+        dispute_program.clone(),
+        dispute.clone(),
+        funder,
+        program_auth.clone(),
+        program.clone(),
+        seeds
+    )?;
+
+    orbit_dispute::cpi::open_dispute(
+        CpiContext::new_with_signer(
+            dispute_program,
+            OpenDispute{
+                new_dispute: dispute,
+                in_transaction: physical_transaction,
+                caller_auth: program_auth,
+                caller_program: program,
+                buyer,
+                seller,
+                payer,
+                system_program
+            },
+            seeds
+        ),
+        threshold
+    )
+}
+
+pub fn record_dispute_ruling(ctx: Context<RecordDisputeRuling>) -> Result<()>{
+    ctx.accounts.physical_transaction.ruling_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+pub fn appeal_dispute_sol(ctx: Context<AppealDisputeSol>) -> Result<()>{
+    let now = Clock::get()?.unix_timestamp;
+    if !appeal_window_open(now, ctx.accounts.physical_transaction.ruling_at, ctx.accounts.market_config.appeal_window){
+        return err!(PhysicalMarketErrors::AppealWindowClosed)
+    };
+
+    let bond_amount = dispute_bond_amount(&ctx.accounts.market_config, ctx.accounts.physical_transaction.metadata.transaction_price);
+    invoke(
+        &transfer(
+            &ctx.accounts.appellant_wallet.key(),
+            &ctx.accounts.appeal_bond.key(),
+            bond_amount
+        ),
+        &[
+            ctx.accounts.appellant_wallet.to_account_info(),
+            ctx.accounts.appeal_bond.to_account_info()
+        ]
+    ).expect("could not post appeal bond");
+
+    ctx.accounts.appeal_bond.physical_transaction = ctx.accounts.physical_transaction.key();
+    ctx.accounts.appeal_bond.opener = ctx.accounts.appellant_account.voter_id;
+    ctx.accounts.appeal_bond.opener_wallet = ctx.accounts.appellant_wallet.key();
+    ctx.accounts.appeal_bond.amount = bond_amount;
+    ctx.accounts.appeal_bond.used = true;
+
+    // the split belonged to the ruling being appealed, the multisig can set a fresh one for the appeal
+    discard_dispute_resolution(&ctx.accounts.dispute_resolution, &ctx.accounts.resolution_payer)?;
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        escalate_dispute_helper(
+            ctx.accounts.dispute_program.to_account_info(),
+            ctx.accounts.phys_dispute.to_account_info(),
+            ctx.accounts.funder.to_account_info(),
+            ctx.accounts.physical_transaction.to_account_info(),
+            ctx.accounts.buyer.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            ctx.accounts.appellant_wallet.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.market_config.appeal_threshold
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.appealed = true;
+    ctx.accounts.physical_transaction.frozen_at = now;
+    Ok(())
+}
+
+pub fn appeal_dispute_spl(ctx: Context<AppealDisputeSpl>) -> Result<()>{
+    let now = Clock::get()?.unix_timestamp;
+    if !appeal_window_open(now, ctx.accounts.physical_transaction.ruling_at, ctx.accounts.market_config.appeal_window){
+        return err!(PhysicalMarketErrors::AppealWindowClosed)
+    };
+
+    let bond_amount = dispute_bond_amount(&ctx.accounts.market_config, ctx.accounts.physical_transaction.metadata.transaction_price);
+    anchor_spl::token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::Transfer{
+                from: ctx.accounts.appellant_token_account.to_account_info(),
+                to: ctx.accounts.bond_escrow.to_account_info(),
+                authority: ctx.accounts.appellant_wallet.to_account_info()
+            }
+        ),
+        bond_amount
+    ).expect("could not post appeal bond. maybe check your balance");
+
+    ctx.accounts.appeal_bond.physical_transaction = ctx.accounts.physical_transaction.key();
+    ctx.accounts.appeal_bond.opener = ctx.accounts.appellant_account.voter_id;
+    ctx.accounts.appeal_bond.opener_wallet = ctx.accounts.appellant_wallet.key();
+    ctx.accounts.appeal_bond.amount = bond_amount;
+    ctx.accounts.appeal_bond.used = true;
+
+    // the split belonged to the ruling being appealed, the multisig can set a fresh one for the appeal
+    discard_dispute_resolution(&ctx.accounts.dispute_resolution, &ctx.accounts.resolution_payer)?;
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        escalate_dispute_helper(
+            ctx.accounts.dispute_program.to_account_info(),
+            ctx.accounts.phys_dispute.to_account_info(),
+            ctx.accounts.funder.to_account_info(),
+            ctx.accounts.physical_transaction.to_account_info(),
+            ctx.accounts.buyer.to_account_info(),
+            ctx.accounts.seller.to_account_info(),
+            ctx.accounts.appellant_wallet.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.market_config.appeal_threshold
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.appealed = true;
+    ctx.accounts.physical_transaction.frozen_at = now;
+    Ok(())
+}
+
+pub fn settle_appeal_bond_sol(ctx: Context<SettleAppealBondSol>) -> Result<()>{
+    if !opener_won(&ctx.accounts.appeal_bond, &ctx.accounts.physical_transaction){
+        let bond_amount = ctx.accounts.appeal_bond.amount;
+        **ctx.accounts.appeal_bond.to_account_info().try_borrow_mut_lamports()? -= bond_amount;
+        **ctx.accounts.counterparty_wallet.to_account_info().try_borrow_mut_lamports()? += bond_amount;
+    };
+    ctx.accounts.appeal_bond.close(ctx.accounts.opener_wallet.to_account_info())
+}
+
+pub fn settle_appeal_bond_spl(ctx: Context<SettleAppealBondSpl>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        let bond_recipient = if opener_won(&ctx.accounts.appeal_bond, &ctx.accounts.physical_transaction){
+            ctx.accounts.opener_token_account.to_account_info()
+        }else{
+            ctx.accounts.counterparty_token_account.to_account_info()
+        };
+
+        orbit_transaction::close_escrow_spl_flat!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.bond_escrow.to_account_info(),
+            bond_recipient,
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.appeal_bond.amount
+        ).expect("couldnt settle appeal bond");
+
+        anchor_spl::token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::CloseAccount{
+                    account: ctx.accounts.bond_escrow.to_account_info(),
+                    destination: ctx.accounts.opener_wallet.to_account_info(),
+                    authority: ctx.accounts.physical_auth.to_account_info()
+                },
+                &[&[b"market_authority", &[*auth_bump]]]
+            )
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.appeal_bond.close(ctx.accounts.opener_wallet.to_account_info())
+}
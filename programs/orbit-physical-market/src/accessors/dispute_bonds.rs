@@ -0,0 +1,474 @@
+use anchor_lang::{
+    prelude::*,
+    AccountsClose,
+    solana_program::{
+        system_instruction::transfer,
+        program::{
+            invoke,
+            invoke_signed
+        },
+    },
+};
+use anchor_spl::token::{
+    TokenAccount,
+    Mint,
+    Token
+};
+use market_accounts::OrbitMarketAccount;
+use orbit_transaction::TransactionState;
+use crate::{
+    PhysicalTransaction,
+    PhysicalMarketConfig,
+    DisputeBond,
+    opener_won_split,
+    bond_for_price,
+    errors::PhysicalMarketErrors
+};
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// DISPUTE BONDS
+/// the opener has to post one before open_dispute, each party in its own slot. its returned
+/// if more than half the split goes their way, otherwise it goes to the counterparty.
+/// a bond that never backed a dispute, or whose dispute closed without a split, is reclaimable
+
+#[derive(Accounts)]
+pub struct PostDisputeBondSol<'info>{
+    #[account(
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery),
+        constraint = physical_transaction.metadata.currency == System::id()
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        init,
+        payer = opener_wallet,
+        space = 100,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &opener_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        constraint =
+        (opener_account.voter_id == physical_transaction.metadata.buyer) ||
+        (opener_account.voter_id == physical_transaction.metadata.seller)
+    )]
+    pub opener_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = opener_account.wallet
+    )]
+    pub opener_wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct PostDisputeBondSpl<'info>{
+    #[account(
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery)
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        init,
+        payer = opener_wallet,
+        space = 100,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &opener_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        init,
+        token::mint = token_mint,
+        token::authority = physical_auth,
+        seeds = [
+            b"dispute_bond_escrow",
+            physical_transaction.key().as_ref(),
+            &opener_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        payer = opener_wallet
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        address = physical_transaction.metadata.currency
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        constraint =
+        (opener_account.voter_id == physical_transaction.metadata.buyer) ||
+        (opener_account.voter_id == physical_transaction.metadata.seller)
+    )]
+    pub opener_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = opener_account.wallet
+    )]
+    pub opener_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        token::authority = opener_wallet
+    )]
+    pub opener_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>
+}
+
+#[derive(Accounts)]
+pub struct SettleDisputeBondSol<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.dispute_buyer_bps.is_some()
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump,
+        has_one = opener_wallet,
+        constraint = dispute_bond.used
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(mut)]
+    pub opener_wallet: SystemAccount<'info>,
+
+    #[account(
+        constraint = counterparty_account.voter_id == counterparty(&dispute_bond, &physical_transaction)
+    )]
+    pub counterparty_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = counterparty_account.wallet
+    )]
+    pub counterparty_wallet: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleDisputeBondSpl<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.dispute_buyer_bps.is_some()
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump,
+        has_one = opener_wallet,
+        constraint = dispute_bond.used
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond_escrow",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub opener_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = opener_wallet
+    )]
+    pub opener_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = counterparty_account.voter_id == counterparty(&dispute_bond, &physical_transaction)
+    )]
+    pub counterparty_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        token::authority = counterparty_account.wallet
+    )]
+    pub counterparty_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimDisputeBondSol<'info>{
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump,
+        has_one = opener_wallet,
+        constraint = bond_reclaimable(&dispute_bond, &physical_transaction)
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(mut)]
+    pub opener_wallet: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimDisputeBondSpl<'info>{
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump,
+        has_one = opener_wallet,
+        constraint = bond_reclaimable(&dispute_bond, &physical_transaction)
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond_escrow",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump
+    )]
+    pub bond_escrow: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub opener_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = opener_wallet
+    )]
+    pub opener_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn dispute_bond_amount(config: &PhysicalMarketConfig, transaction_price: u64) -> u64{
+    bond_for_price(transaction_price, config.dispute_bond_bps, config.dispute_bond_floor)
+}
+
+pub fn counterparty(bond: &DisputeBond, physical_transaction: &PhysicalTransaction) -> u64{
+    if bond.opener == physical_transaction.metadata.buyer{
+        physical_transaction.metadata.seller
+    }else{
+        physical_transaction.metadata.buyer
+    }
+}
+
+pub fn opener_won(bond: &DisputeBond, physical_transaction: &PhysicalTransaction) -> bool{
+    match physical_transaction.dispute_buyer_bps{
+        Some(buyer_bps) => opener_won_split(bond.opener == physical_transaction.metadata.buyer, buyer_bps),
+        None => false
+    }
+}
+
+/// never backed a dispute, or the tx closed without a split to judge it by
+pub fn bond_reclaimable(bond: &DisputeBond, physical_transaction: &PhysicalTransaction) -> bool{
+    !bond.used || (
+        (physical_transaction.metadata.transaction_state == TransactionState::Closed) &&
+        physical_transaction.dispute_buyer_bps.is_none()
+    )
+}
+
+pub fn post_dispute_bond_sol(ctx: Context<PostDisputeBondSol>) -> Result<()>{
+    let bond_amount = dispute_bond_amount(&ctx.accounts.market_config, ctx.accounts.physical_transaction.metadata.transaction_price);
+
+    invoke(
+        &transfer(
+            &ctx.accounts.opener_wallet.key(),
+            &ctx.accounts.dispute_bond.key(),
+            bond_amount
+        ),
+        &[
+            ctx.accounts.opener_wallet.to_account_info(),
+            ctx.accounts.dispute_bond.to_account_info()
+        ]
+    ).expect("could not post dispute bond");
+
+    ctx.accounts.dispute_bond.physical_transaction = ctx.accounts.physical_transaction.key();
+    ctx.accounts.dispute_bond.opener = ctx.accounts.opener_account.voter_id;
+    ctx.accounts.dispute_bond.opener_wallet = ctx.accounts.opener_wallet.key();
+    ctx.accounts.dispute_bond.amount = bond_amount;
+    ctx.accounts.dispute_bond.used = false;
+    Ok(())
+}
+
+pub fn post_dispute_bond_spl(ctx: Context<PostDisputeBondSpl>) -> Result<()>{
+    let bond_amount = dispute_bond_amount(&ctx.accounts.market_config, ctx.accounts.physical_transaction.metadata.transaction_price);
+
+    anchor_spl::token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            anchor_spl::token::Transfer{
+                from: ctx.accounts.opener_token_account.to_account_info(),
+                to: ctx.accounts.bond_escrow.to_account_info(),
+                authority: ctx.accounts.opener_wallet.to_account_info()
+            }
+        ),
+        bond_amount
+    ).expect("could not post dispute bond. maybe check your balance");
+
+    ctx.accounts.dispute_bond.physical_transaction = ctx.accounts.physical_transaction.key();
+    ctx.accounts.dispute_bond.opener = ctx.accounts.opener_account.voter_id;
+    ctx.accounts.dispute_bond.opener_wallet = ctx.accounts.opener_wallet.key();
+    ctx.accounts.dispute_bond.amount = bond_amount;
+    ctx.accounts.dispute_bond.used = false;
+    Ok(())
+}
+
+pub fn settle_dispute_bond_sol(ctx: Context<SettleDisputeBondSol>) -> Result<()>{
+    if !opener_won(&ctx.accounts.dispute_bond, &ctx.accounts.physical_transaction){
+        let bond_amount = ctx.accounts.dispute_bond.amount;
+        **ctx.accounts.dispute_bond.to_account_info().try_borrow_mut_lamports()? -= bond_amount;
+        **ctx.accounts.counterparty_wallet.to_account_info().try_borrow_mut_lamports()? += bond_amount;
+    };
+    // rent and whatever is left of the bond go back to the opener
+    ctx.accounts.dispute_bond.close(ctx.accounts.opener_wallet.to_account_info())
+}
+
+pub fn settle_dispute_bond_spl(ctx: Context<SettleDisputeBondSpl>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        let bond_recipient = if opener_won(&ctx.accounts.dispute_bond, &ctx.accounts.physical_transaction){
+            ctx.accounts.opener_token_account.to_account_info()
+        }else{
+            ctx.accounts.counterparty_token_account.to_account_info()
+        };
+
+        orbit_transaction::close_escrow_spl_flat!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.bond_escrow.to_account_info(),
+            bond_recipient,
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.dispute_bond.amount
+        ).expect("couldnt settle dispute bond");
+
+        anchor_spl::token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::CloseAccount{
+                    account: ctx.accounts.bond_escrow.to_account_info(),
+                    destination: ctx.accounts.opener_wallet.to_account_info(),
+                    authority: ctx.accounts.physical_auth.to_account_info()
+                },
+                &[&[b"market_authority", &[*auth_bump]]]
+            )
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.dispute_bond.close(ctx.accounts.opener_wallet.to_account_info())
+}
+
+pub fn reclaim_dispute_bond_sol(ctx: Context<ReclaimDisputeBondSol>) -> Result<()>{
+    // bond lamports ride along with the rent
+    ctx.accounts.dispute_bond.close(ctx.accounts.opener_wallet.to_account_info())
+}
+
+pub fn reclaim_dispute_bond_spl(ctx: Context<ReclaimDisputeBondSpl>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::close_escrow_spl_flat!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.bond_escrow.to_account_info(),
+            ctx.accounts.opener_token_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.dispute_bond.amount
+        ).expect("couldnt reclaim dispute bond");
+
+        anchor_spl::token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::CloseAccount{
+                    account: ctx.accounts.bond_escrow.to_account_info(),
+                    destination: ctx.accounts.opener_wallet.to_account_info(),
+                    authority: ctx.accounts.physical_auth.to_account_info()
+                },
+                &[&[b"market_authority", &[*auth_bump]]]
+            )
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.dispute_bond.close(ctx.accounts.opener_wallet.to_account_info())
+}
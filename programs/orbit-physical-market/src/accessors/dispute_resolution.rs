@@ -0,0 +1,102 @@
+use anchor_lang::{
+    prelude::*,
+    AccountsClose
+};
+use orbit_transaction::TransactionState;
+use crate::{
+    PhysicalTransaction,
+    DisputeResolution,
+    ruling_buyer_bps,
+    errors::PhysicalMarketErrors
+};
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// SPLIT DISPUTE RESOLUTIONS (multisig only)
+/// orbit_dispute can only pick a side, so splits live in their own account.
+/// a split only counts if it leaves the side jurors favored with more than half
+
+#[derive(Accounts)]
+pub struct SetDisputeResolution<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 100,
+        seeds = [
+            b"dispute_resolution",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub dispute_resolution: Account<'info, DisputeResolution>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct CloseDisputeResolution<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_resolution",
+            physical_transaction.key().as_ref()
+        ],
+        bump,
+        has_one = payer
+    )]
+    pub dispute_resolution: Account<'info, DisputeResolution>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+}
+
+pub fn set_dispute_resolution(ctx: Context<SetDisputeResolution>, buyer_bps: u16) -> Result<()>{
+    if buyer_bps > 10000{
+        return err!(PhysicalMarketErrors::InvalidResolution)
+    };
+    ctx.accounts.dispute_resolution.physical_transaction = ctx.accounts.physical_transaction.key();
+    ctx.accounts.dispute_resolution.payer = ctx.accounts.payer.key();
+    ctx.accounts.dispute_resolution.buyer_bps = buyer_bps;
+    Ok(())
+}
+
+pub fn close_dispute_resolution(ctx: Context<CloseDisputeResolution>) -> Result<()>{
+    ctx.accounts.dispute_resolution.close(ctx.accounts.payer.to_account_info())
+}
+
+/// drops a split whose ruling was appealed. nothing to do if none was set
+pub fn discard_dispute_resolution<'info>(dispute_resolution: &AccountInfo<'info>, resolution_payer: &Option<SystemAccount<'info>>) -> Result<()>{
+    if (dispute_resolution.owner != &crate::ID) || dispute_resolution.data_is_empty(){
+        return Ok(())
+    };
+    let resolution = Account::<DisputeResolution>::try_from(dispute_resolution)?;
+    match resolution_payer{
+        Some(payer) if payer.key() == resolution.payer => resolution.close(payer.to_account_info()),
+        _ => err!(PhysicalMarketErrors::InvalidResolution)
+    }
+}
+
+/// buyers share of escrow. without a usable split the whole thing goes to whoever the dispute favored
+pub fn resolution_buyer_bps(dispute_resolution: &AccountInfo, favors_buyer: bool) -> u16{
+    ruling_buyer_bps(
+        Account::<DisputeResolution>::try_from(dispute_resolution).ok().map(|resolution| resolution.buyer_bps),
+        favors_buyer
+    )
+}
@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+use crate::{
+    PhysicalMarketConfig,
+    PhysicalFeeConfig,
+    FeeSchedule,
+    FeeTier,
+    DeliveryOracle,
+    errors::PhysicalMarketErrors
+};
+
+///////////////////////////////////////////////////////////////////
+/// MARKET CONFIG (multisig only)
+
+#[derive(Accounts)]
+pub struct InitMarketConfig<'info>{
+    #[account(
+        init,
+        payer = payer,
+        space = 100,
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateMarketConfig<'info>{
+    #[account(
+        mut,
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitFeeConfig<'info>{
+    #[account(
+        init,
+        payer = payer,
+        space = 200,
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeConfig<'info>{
+    #[account(
+        mut,
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitDeliveryOracle<'info>{
+    #[account(
+        init,
+        payer = payer,
+        space = 100,
+        seeds = [b"delivery_oracle"],
+        bump
+    )]
+    pub delivery_oracle: Account<'info, DeliveryOracle>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateDeliveryOracle<'info>{
+    #[account(
+        mut,
+        seeds = [b"delivery_oracle"],
+        bump
+    )]
+    pub delivery_oracle: Account<'info, DeliveryOracle>,
+
+    #[account(
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+pub fn init_market_config(ctx: Context<InitMarketConfig>, inspection_window: i64, default_handling_time: i64) -> Result<()>{
+    ctx.accounts.market_config.inspection_window = inspection_window;
+    ctx.accounts.market_config.default_handling_time = default_handling_time;
+    Ok(())
+}
+
+pub fn set_inspection_window(ctx: Context<UpdateMarketConfig>, inspection_window: i64) -> Result<()>{
+    ctx.accounts.market_config.inspection_window = inspection_window;
+    Ok(())
+}
+
+pub fn set_default_handling_time(ctx: Context<UpdateMarketConfig>, default_handling_time: i64) -> Result<()>{
+    ctx.accounts.market_config.default_handling_time = default_handling_time;
+    Ok(())
+}
+
+pub fn set_max_handling_time(ctx: Context<UpdateMarketConfig>, max_handling_time: i64) -> Result<()>{
+    if max_handling_time < 0{
+        return err!(PhysicalMarketErrors::InvalidHandlingTime)
+    };
+    ctx.accounts.market_config.max_handling_time = max_handling_time;
+    Ok(())
+}
+
+pub fn set_return_window(ctx: Context<UpdateMarketConfig>, return_window: i64) -> Result<()>{
+    if return_window < 0{
+        return err!(PhysicalMarketErrors::InvalidReturnState)
+    };
+    ctx.accounts.market_config.return_window = return_window;
+    Ok(())
+}
+
+pub fn set_dispute_bond(ctx: Context<UpdateMarketConfig>, dispute_bond_bps: u16, dispute_bond_floor: u64) -> Result<()>{
+    if dispute_bond_bps > 10000{
+        return err!(PhysicalMarketErrors::InvalidResolution)
+    };
+    ctx.accounts.market_config.dispute_bond_bps = dispute_bond_bps;
+    ctx.accounts.market_config.dispute_bond_floor = dispute_bond_floor;
+    Ok(())
+}
+
+/// zero disables either window: no post delivery dispute deadline, no default judgment
+pub fn set_dispute_windows(ctx: Context<UpdateMarketConfig>, dispute_window: i64, resolution_window: i64, default_buyer_bps: u16) -> Result<()>{
+    if (default_buyer_bps > 10000) || (dispute_window < 0) || (resolution_window < 0){
+        return err!(PhysicalMarketErrors::InvalidResolution)
+    };
+    ctx.accounts.market_config.dispute_window = dispute_window;
+    ctx.accounts.market_config.resolution_window = resolution_window;
+    ctx.accounts.market_config.default_buyer_bps = default_buyer_bps;
+    Ok(())
+}
+
+pub fn set_appeals(ctx: Context<UpdateMarketConfig>, appeal_window: i64, appeal_threshold: u8) -> Result<()>{
+    if appeal_window < 0{
+        return err!(PhysicalMarketErrors::InvalidResolution)
+    };
+    ctx.accounts.market_config.appeal_window = appeal_window;
+    ctx.accounts.market_config.appeal_threshold = appeal_threshold;
+    Ok(())
+}
+
+/// the reflink share is paid twice (reflink owner and buyer) out of the platform fee
+fn check_fee_schedule(fees: &FeeSchedule) -> Result<()>{
+    if (fees.platform_fee_bps > 10000) ||
+        (fees.dispute_discount_bps > 10000) ||
+        (2 * fees.reflink_share_bps > fees.platform_fee_bps)
+    {
+        return err!(PhysicalMarketErrors::InvalidFeeSchedule)
+    };
+    Ok(())
+}
+
+pub fn init_fee_config(ctx: Context<InitFeeConfig>, fees: FeeSchedule) -> Result<()>{
+    check_fee_schedule(&fees)?;
+    ctx.accounts.fee_config.fees = fees;
+    Ok(())
+}
+
+pub fn set_fee_schedule(ctx: Context<UpdateFeeConfig>, fees: FeeSchedule) -> Result<()>{
+    check_fee_schedule(&fees)?;
+    ctx.accounts.fee_config.fees = fees;
+    Ok(())
+}
+
+pub fn set_fee_tiers(ctx: Context<UpdateFeeConfig>, tiers: Vec<FeeTier>) -> Result<()>{
+    if (tiers.len() > PhysicalFeeConfig::MAX_FEE_TIERS) || tiers.iter().any(|tier| tier.platform_fee_bps > 10000){
+        return err!(PhysicalMarketErrors::InvalidFeeSchedule)
+    };
+    ctx.accounts.fee_config.tiers = tiers;
+    Ok(())
+}
+
+pub fn init_delivery_oracle(ctx: Context<InitDeliveryOracle>, signer: Pubkey) -> Result<()>{
+    ctx.accounts.delivery_oracle.signer = signer;
+    Ok(())
+}
+
+pub fn set_delivery_oracle(ctx: Context<UpdateDeliveryOracle>, signer: Pubkey) -> Result<()>{
+    ctx.accounts.delivery_oracle.signer = signer;
+    Ok(())
+}
@@ -1,5 +1,38 @@
 pub mod phys_tx_common;
 pub use phys_tx_common::*;
 
+pub mod phys_tx_returns;
+pub use phys_tx_returns::*;
+
+pub mod phys_tx_evidence;
+pub use phys_tx_evidence::*;
+
+pub mod dispute_resolution;
+pub use dispute_resolution::*;
+
+pub mod dispute_bonds;
+pub use dispute_bonds::*;
+
+pub mod dispute_appeals;
+pub use dispute_appeals::*;
+
+pub mod phys_tx_cart;
+pub use phys_tx_cart::*;
+
+pub mod seller_payout;
+pub use seller_payout::*;
+
+pub mod seller_keys;
+pub use seller_keys::*;
+
+pub mod coupons;
+pub use coupons::*;
+
+pub mod market_config;
+pub use market_config::*;
+
+pub mod payouts;
+pub use payouts::*;
+
 pub mod tx_accessors;
 pub use tx_accessors::*;
\ No newline at end of file
@@ -0,0 +1,263 @@
+/////////////////////////////////////////////////////////////////////////////////////////////
+// PAYOUT MATH
+// pure timing and escrow arithmetic shared by the sol and spl instruction paths
+
+use crate::FeeSchedule;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct EscrowSplit{
+    pub buyer: u64,
+    pub fee: u64, // platform fee, only ever taken from the sellers part
+    pub seller: u64,
+}
+
+/// auto release waits out the inspection window after shipment, and the post delivery
+/// dispute window once delivery is confirmed. a zero dispute window means no deadline
+pub fn release_due(now: i64, shipped_at: i64, inspection_window: i64, delivered_at: i64, dispute_window: i64) -> bool{
+    if now < shipped_at.saturating_add(inspection_window){
+        return false
+    };
+    (delivered_at == 0) || (dispute_window == 0) || (now > delivered_at.saturating_add(dispute_window))
+}
+
+/// total for (unit price, quantity) lines. none if it doesnt fit in a u64
+pub fn order_total<I: IntoIterator<Item = (u64, u32)>>(lines: I) -> Option<u64>{
+    lines.into_iter().try_fold(0u64, |total, (unit_price, quantity)|
+        total.checked_add(unit_price.checked_mul(quantity as u64)?)
+    )
+}
+
+/// jurors pick the side. a multisig split can only hand part of the winners share back to
+/// the loser, the winner has to keep more than half. any other split is ignored
+pub fn ruling_buyer_bps(split: Option<u16>, favors_buyer: bool) -> u16{
+    match split{
+        Some(bps) if favors_buyer && (bps > 5000) && (bps <= 10000) => bps,
+        Some(bps) if !favors_buyer && (bps < 5000) => bps,
+        _ => if favors_buyer {10000} else {0}
+    }
+}
+
+/// bond a dispute opener or appellant posts, never below the floor
+pub fn bond_for_price(transaction_price: u64, bond_bps: u16, bond_floor: u64) -> u64{
+    (((transaction_price as u128) * (bond_bps.min(10000) as u128) / 10000) as u64).max(bond_floor)
+}
+
+/// the opener needs strictly more than half the split going their way to get a bond back.
+/// an even split counts as a loss for whoever opened
+pub fn opener_won_split(opener_is_buyer: bool, buyer_bps: u16) -> bool{
+    if opener_is_buyer{
+        buyer_bps > 5000
+    }else{
+        buyer_bps < 5000
+    }
+}
+
+/// escrow split once a dispute closes
+pub fn dispute_split(escrow: u64, buyer_bps: u16, rate: u8, fees: &FeeSchedule) -> EscrowSplit{
+    let buyer = ((escrow as u128) * (buyer_bps.min(10000) as u128) / 10000) as u64;
+    let seller_share = escrow - buyer;
+    let fee = if rate == 95{
+        fees.platform_fee(seller_share)
+    }else{
+        0
+    };
+    EscrowSplit{
+        buyer,
+        fee,
+        seller: seller_share - fee
+    }
+}
+
+/// post delivery disputes close after the dispute window. zero means no deadline
+pub fn dispute_window_open(now: i64, delivered_at: i64, dispute_window: i64) -> bool{
+    (dispute_window == 0) || (now <= delivered_at.saturating_add(dispute_window))
+}
+
+/// jurors get the resolution window before the default split can be forced. zero disables default judgment
+pub fn default_judgment_due(now: i64, frozen_at: i64, resolution_window: i64) -> bool{
+    (resolution_window > 0) && (now > frozen_at.saturating_add(resolution_window))
+}
+
+/// the loser can appeal until appeal_window seconds after the ruling was recorded
+pub fn appeal_window_open(now: i64, ruling_at: i64, appeal_window: i64) -> bool{
+    now <= ruling_at.saturating_add(appeal_window)
+}
+
+/// none if the refund is more than what sits in escrow
+pub fn partial_refund_split(escrow: u64, buyer_amount: u64, rate: u8, fees: &FeeSchedule) -> Option<EscrowSplit>{
+    let seller_share = escrow.checked_sub(buyer_amount)?;
+    let fee = if rate == 95{
+        fees.platform_fee(seller_share)
+    }else{
+        0
+    };
+    Some(EscrowSplit{
+        buyer: buyer_amount,
+        fee,
+        seller: seller_share - fee
+    })
+}
+
+#[cfg(test)]
+mod tests{
+    use super::*;
+
+    const FEES: FeeSchedule = FeeSchedule{
+        platform_fee_bps: 500,
+        reflink_share_bps: 100,
+        dispute_discount_bps: 500
+    };
+
+    #[test]
+    fn release_waits_for_inspection_window(){
+        assert!(!release_due(199, 100, 100, 0, 50));
+        assert!(release_due(200, 100, 100, 0, 50));
+    }
+
+    #[test]
+    fn release_waits_for_dispute_window_after_delivery(){
+        // delivered at 190, disputes allowed until 240
+        assert!(!release_due(200, 100, 100, 190, 50));
+        assert!(!release_due(240, 100, 100, 190, 50));
+        assert!(release_due(241, 100, 100, 190, 50));
+    }
+
+    #[test]
+    fn release_ignores_disabled_dispute_window(){
+        assert!(release_due(200, 100, 100, 190, 0));
+    }
+
+    #[test]
+    fn dispute_window_closes_after_delivery(){
+        assert!(dispute_window_open(150, 100, 50));
+        assert!(!dispute_window_open(151, 100, 50));
+        assert!(dispute_window_open(10_000, 100, 0));
+    }
+
+    #[test]
+    fn default_judgment_waits_out_resolution_window(){
+        assert!(!default_judgment_due(150, 100, 50));
+        assert!(default_judgment_due(151, 100, 50));
+    }
+
+    #[test]
+    fn default_judgment_disabled_by_zero_window(){
+        assert!(!default_judgment_due(100, 100, 0));
+        assert!(!default_judgment_due(i64::MAX, 0, 0));
+    }
+
+    #[test]
+    fn appeal_window_runs_from_ruling(){
+        assert!(appeal_window_open(150, 100, 50));
+        assert!(!appeal_window_open(151, 100, 50));
+    }
+
+    #[test]
+    fn appeal_window_saturates_instead_of_overflowing(){
+        assert!(appeal_window_open(i64::MAX, 100, i64::MAX));
+    }
+
+    #[test]
+    fn order_total_sums_lines(){
+        assert_eq!(order_total(vec![(1_000, 3), (250, 2)]), Some(3_500));
+        assert_eq!(order_total(Vec::new()), Some(0));
+    }
+
+    #[test]
+    fn order_total_rejects_overflow(){
+        assert_eq!(order_total(vec![(u64::MAX, 2)]), None);
+        assert_eq!(order_total(vec![(u64::MAX, 1), (1, 1)]), None);
+    }
+
+    #[test]
+    fn ruling_without_split_awards_everything(){
+        assert_eq!(ruling_buyer_bps(None, true), 10000);
+        assert_eq!(ruling_buyer_bps(None, false), 0);
+    }
+
+    #[test]
+    fn ruling_split_refines_toward_the_winner(){
+        assert_eq!(ruling_buyer_bps(Some(7000), true), 7000);
+        assert_eq!(ruling_buyer_bps(Some(3000), false), 3000);
+    }
+
+    #[test]
+    fn ruling_split_cant_flip_the_winner(){
+        assert_eq!(ruling_buyer_bps(Some(3000), true), 10000);
+        assert_eq!(ruling_buyer_bps(Some(5000), true), 10000);
+        assert_eq!(ruling_buyer_bps(Some(7000), false), 0);
+        assert_eq!(ruling_buyer_bps(Some(5000), false), 0);
+        assert_eq!(ruling_buyer_bps(Some(10001), true), 10000);
+    }
+
+    #[test]
+    fn bond_is_bps_of_price_with_a_floor(){
+        assert_eq!(bond_for_price(10_000, 500, 100), 500);
+        assert_eq!(bond_for_price(1_000, 500, 100), 100);
+    }
+
+    #[test]
+    fn bond_doesnt_overflow_large_prices(){
+        assert_eq!(bond_for_price(u64::MAX, 10000, 0), u64::MAX);
+        assert_eq!(bond_for_price(u64::MAX, 5000, 0), u64::MAX / 2);
+    }
+
+    #[test]
+    fn opener_wins_with_majority_of_split(){
+        assert!(opener_won_split(true, 5001));
+        assert!(opener_won_split(false, 4999));
+        assert!(!opener_won_split(true, 4999));
+        assert!(!opener_won_split(false, 5001));
+    }
+
+    #[test]
+    fn even_split_loses_for_either_opener(){
+        assert!(!opener_won_split(true, 5000));
+        assert!(!opener_won_split(false, 5000));
+    }
+
+    #[test]
+    fn dispute_split_takes_fee_from_seller_share(){
+        assert_eq!(dispute_split(10_000, 3_000, 95, &FEES), EscrowSplit{buyer: 3_000, fee: 350, seller: 6_650});
+        assert_eq!(dispute_split(10_000, 3_000, 100, &FEES), EscrowSplit{buyer: 3_000, fee: 0, seller: 7_000});
+    }
+
+    #[test]
+    fn dispute_split_full_sides(){
+        assert_eq!(dispute_split(10_000, 10000, 95, &FEES), EscrowSplit{buyer: 10_000, fee: 0, seller: 0});
+        assert_eq!(dispute_split(10_000, 0, 95, &FEES), EscrowSplit{buyer: 0, fee: 500, seller: 9_500});
+    }
+
+    #[test]
+    fn dispute_split_doesnt_overflow_large_escrow(){
+        let split = dispute_split(u64::MAX, 5000, 100, &FEES);
+        assert_eq!(split.buyer + split.seller, u64::MAX);
+    }
+
+    #[test]
+    fn partial_refund_charges_fee_on_seller_part_only(){
+        assert_eq!(
+            partial_refund_split(10_000, 2_000, 95, &FEES),
+            Some(EscrowSplit{buyer: 2_000, fee: 400, seller: 7_600})
+        );
+    }
+
+    #[test]
+    fn partial_refund_skips_fee_when_discount_used(){
+        assert_eq!(
+            partial_refund_split(10_000, 2_000, 100, &FEES),
+            Some(EscrowSplit{buyer: 2_000, fee: 0, seller: 8_000})
+        );
+    }
+
+    #[test]
+    fn partial_refund_full_and_zero(){
+        assert_eq!(partial_refund_split(10_000, 10_000, 95, &FEES), Some(EscrowSplit{buyer: 10_000, fee: 0, seller: 0}));
+        assert_eq!(partial_refund_split(10_000, 0, 95, &FEES), Some(EscrowSplit{buyer: 0, fee: 500, seller: 9_500}));
+    }
+
+    #[test]
+    fn partial_refund_cant_exceed_escrow(){
+        assert_eq!(partial_refund_split(10_000, 10_001, 95, &FEES), None);
+    }
+}
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use market_accounts::OrbitMarketAccount;
+use orbit_product::{
+    PhysicalProduct,
+    ListingsStruct,
+    program::OrbitProduct
+};
+use orbit_transaction::TransactionState;
+use crate::{
+    PhysicalTransaction,
+    CartItem,
+    CartSettlement,
+    errors::PhysicalMarketErrors,
+    update_product_quantity_helper,
+    increment_times_sold_helper,
+    open_sol_quantity,
+    open_spl_quantity,
+
+    OpenPhysicalTransactionSol,
+    OpenPhysicalTransactionSpl,
+
+    program::OrbitPhysicalMarket
+};
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// CART CHECKOUT
+/// quantities[0] is for phys_product, the rest line up with the products in remaining_accounts
+
+pub fn take_cart_inventory<'a>(product_accounts: &[AccountInfo<'a>], quantities: &[u32], primary_product: u64, seller: u64, currency: Pubkey, product_program: AccountInfo<'a>, vendor_account: AccountInfo<'a>, vendor_listings: AccountInfo<'a>, program_auth: AccountInfo<'a>, program: AccountInfo<'a>, seeds: &[&[&[u8]]]) -> Result<Vec<CartItem>>{
+    if product_accounts.is_empty() || product_accounts.len() > PhysicalTransaction::MAX_CART_ITEMS || quantities.len() != product_accounts.len() + 1{
+        return err!(PhysicalMarketErrors::InvalidCartItems)
+    };
+    if quantities.iter().map(|quantity| *quantity as u64).sum::<u64>() > PhysicalTransaction::MAX_QUANTITY as u64{
+        return err!(PhysicalMarketErrors::InvalidQuantity)
+    };
+
+    let mut cart: Vec<CartItem> = Vec::with_capacity(product_accounts.len());
+    for (product_account, quantity) in product_accounts.iter().zip(quantities[1..].iter()){
+        let product = Account::<PhysicalProduct>::try_from(product_account)?;
+        if product.metadata.owner_catalog != seller{
+            return err!(PhysicalMarketErrors::InvalidSellerForListing)
+        };
+        if (product.metadata.index == primary_product) || cart.iter().any(|item| item.product == product.metadata.index){
+            return err!(PhysicalMarketErrors::InvalidCartItems)
+        };
+        if *quantity == 0 || *quantity > product.quantity{
+            return err!(PhysicalMarketErrors::InvalidQuantity)
+        };
+        if product.metadata.currency != currency{
+            return err!(PhysicalMarketErrors::ListingPriceMismatch)
+        };
+
+        update_product_quantity_helper(
+            product_program.clone(),
+            product_account.clone(),
+            vendor_account.clone(),
+            vendor_listings.clone(),
+            program_auth.clone(),
+            program.clone(),
+            seeds,
+            product.quantity - quantity
+        )?;
+
+        cart.push(CartItem{
+            product: product.metadata.index,
+            quantity: *quantity,
+            unit_price: product.metadata.price
+        });
+    };
+    Ok(cart)
+}
+
+pub fn open_cart_sol<'a>(ctx: Context<'_, '_, '_, 'a, OpenPhysicalTransactionSol<'a>>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, quantities: Vec<u32>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        ctx.accounts.physical_transaction.cart = take_cart_inventory(
+            ctx.remaining_accounts,
+            &quantities,
+            ctx.accounts.phys_product.metadata.index,
+            ctx.accounts.seller_market_account.voter_id,
+            System::id(),
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.seller_market_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    open_sol_quantity(ctx, seller_index, buyer_index, price, use_discount, quantities[0])
+}
+
+pub fn open_cart_spl<'a>(ctx: Context<'_, '_, '_, 'a, OpenPhysicalTransactionSpl<'a>>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, quantities: Vec<u32>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        ctx.accounts.physical_transaction.cart = take_cart_inventory(
+            ctx.remaining_accounts,
+            &quantities,
+            ctx.accounts.phys_product.metadata.index,
+            ctx.accounts.seller_market_account.voter_id,
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.seller_market_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    open_spl_quantity(ctx, seller_index, buyer_index, price, use_discount, quantities[0])
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// CART SETTLEMENT
+/// close/refund paths only touch phys_product. the rest of the cart gets
+/// restocked or marked sold here, with the cart products passed in cart order
+
+#[derive(Accounts)]
+pub struct SettleCartItems<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.cart_settlement != CartSettlement::None
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+}
+
+pub fn settle_cart_items<'a>(ctx: Context<'_, '_, '_, 'a, SettleCartItems<'a>>) -> Result<()>{
+    if ctx.remaining_accounts.len() != ctx.accounts.physical_transaction.cart.len(){
+        return err!(PhysicalMarketErrors::InvalidCartItems)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        for (item, product_account) in ctx.accounts.physical_transaction.cart.iter().zip(ctx.remaining_accounts.iter()){
+            let product = Account::<PhysicalProduct>::try_from(product_account)?;
+            if product.metadata.index != item.product || product.metadata.owner_catalog != ctx.accounts.seller_account.voter_id{
+                return err!(PhysicalMarketErrors::InvalidCartItems)
+            };
+
+            match ctx.accounts.physical_transaction.cart_settlement{
+                CartSettlement::Restock => update_product_quantity_helper(
+                    ctx.accounts.product_program.to_account_info(),
+                    product_account.clone(),
+                    ctx.accounts.seller_account.to_account_info(),
+                    ctx.accounts.seller_listings.to_account_info(),
+                    ctx.accounts.physical_auth.to_account_info(),
+                    ctx.accounts.physical_program.to_account_info(),
+                    &[&[b"market_authority", &[*auth_bump]]],
+                    product.quantity + item.quantity
+                ),
+                CartSettlement::Sold => increment_times_sold_helper(
+                    ctx.accounts.product_program.to_account_info(),
+                    product_account.clone(),
+                    ctx.accounts.physical_auth.to_account_info(),
+                    ctx.accounts.physical_program.to_account_info(),
+                    &[&[b"market_authority", &[*auth_bump]]],
+                    item.quantity
+                ),
+                CartSettlement::None => Ok(())
+            }?;
+        };
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.cart_settlement = CartSettlement::None;
+    Ok(())
+}
@@ -2,6 +2,7 @@ use anchor_lang::{
     prelude::*,
     AccountsClose,
     solana_program::{
+        hash::hashv,
         system_instruction::transfer,
         program::{
             invoke,
@@ -36,10 +37,43 @@ use crate::{
     FundEscrowSpl,
     ClosePhysicalDisputeSpl,
 
+    BuyerCancelSol,
+    BuyerCancelSpl,
+    AutoReleaseSol,
+    AutoReleaseSpl,
+    ReclaimUnshippedSol,
+    ReclaimUnshippedSpl,
+    AcceptPartialRefundSol,
+    AcceptPartialRefundSpl,
+    RefundProposal,
+    ReturnState,
+    CartItem,
+    CartSettlement,
+    ShippingEnvelope,
+    ShipmentRecord,
+    Carrier,
+    DeliveryOracle,
+    FeeSchedule,
+    redeem_coupon,
+    restore_coupon_use,
+    encryption_key_version,
+    resolution_buyer_bps,
+    DisputeBond,
+    PhysicalMarketConfig,
+    release_due,
+    order_total,
+    partial_refund_split,
+    dispute_window_open,
+    default_judgment_due,
+    appeal_window_open,
+    dispute_split,
+
     program::OrbitPhysicalMarket, SellerEarlyDeclineSpl, SellerEarlyDeclineSol
 };
 use orbit_dispute::{
     structs::dispute_trait::OrbitDisputableTrait,
+    DisputeState,
+    OrbitDispute,
     program::Dispute,
     cpi::accounts::{
         OpenDispute,
@@ -59,6 +93,7 @@ pub struct CloseTransactionAccount<'info>{
     #[account(
         mut,
         constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.cart_settlement == CartSettlement::None
     )]
     pub physical_transaction: Account<'info, PhysicalTransaction>,
 
@@ -84,228 +119,424 @@ pub struct CloseTransactionAccount<'info>{
     pub buyer_wallet: SystemAccount<'info>
 }
 
-impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, OpenPhysicalTransactionSol<'a>, OpenPhysicalTransactionSpl<'b>, ClosePhysicalTransactionSol<'c>, ClosePhysicalTransactionSpl<'d>, FundEscrowSol<'e>, FundEscrowSpl<'f>, CloseTransactionAccount<'g>, SellerEarlyDeclineSol<'h>, SellerEarlyDeclineSpl<'i>> for PhysicalTransaction{
-    fn open_sol(ctx: Context<OpenPhysicalTransactionSol>, seller_index: u8, buyer_index: u8, mut price: u64, use_discount: bool) -> Result<()>{
-        
-        let auth_bump: &u8;
-        if let Some(ab) = ctx.bumps.get("physical_auth"){
-            auth_bump = ab
-        }else{
-            return err!(PhysicalMarketErrors::InvalidAuthBump)
-        };
+pub fn update_product_quantity_helper<'a>(product_program: AccountInfo<'a>, product: AccountInfo<'a>, vendor_account: AccountInfo<'a>, vendor_listings: AccountInfo<'a>, program_auth: AccountInfo<'a>, program: AccountInfo<'a>, seeds: &[&[&[u8]]], quantity: u32) -> Result<()>{
+    orbit_product::cpi::update_product_quantity_internal(
+        CpiContext::new_with_signer(
+            product_program,
+            orbit_product::cpi::accounts::UpdatePhysicalQuantityInternal{
+                product,
+                vendor_account,
+                vendor_listings,
+                caller_auth: program_auth,
+                caller: program
+            },
+            seeds
+        ),
+        quantity
+    )
+}
+
+pub fn increment_times_sold_helper<'a>(product_program: AccountInfo<'a>, product: AccountInfo<'a>, program_auth: AccountInfo<'a>, program: AccountInfo<'a>, seeds: &[&[&[u8]]], quantity: u32) -> Result<()>{
+    for _ in 0..quantity{
+        orbit_product::cpi::physical_increment_times_sold(
+            CpiContext::new_with_signer(
+                product_program.clone(),
+                orbit_product::cpi::accounts::IncrementPhysicalSoldInternal{
+                    product: product.clone(),
+                    caller_auth: program_auth.clone(),
+                    caller: program.clone()
+                },
+                seeds
+            )
+        )?;
+    };
+    Ok(())
+}
+
+pub fn clear_transaction_logs_helper<'a>(transaction_program: AccountInfo<'a>, seller_transactions_log: AccountInfo<'a>, buyer_transactions_log: AccountInfo<'a>, program_auth: AccountInfo<'a>, program: AccountInfo<'a>, seeds: &[&[&[u8]]], seller_tx_index: u8, buyer_tx_index: u8) -> Result<()>{
+    orbit_transaction::cpi::clear_seller_physical_transaction(
+        CpiContext::new_with_signer(
+            transaction_program.clone(),
+            orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
+                transactions_log: seller_transactions_log,
+                caller_auth: program_auth.clone(),
+                caller: program.clone()
+            },
+            seeds
+        ),
+        seller_tx_index
+    )?;
+
+    orbit_transaction::cpi::clear_buyer_physical_transaction(
+        CpiContext::new_with_signer(
+            transaction_program,
+            orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
+                transactions_log: buyer_transactions_log,
+                caller_auth: program_auth,
+                caller: program
+            },
+            seeds
+        ),
+        buyer_tx_index
+    )
+}
 
-        if use_discount && ctx.accounts.buyer_market_account.dispute_discounts > 0{
-            ctx.accounts.physical_transaction.metadata.rate = 100;
-            price = price * 95 / 100;
+/// pays out the platform fee (and reflink share) then sends the rest to the seller
+pub fn release_escrow_sol_helper<'a>(escrow_account: AccountInfo<'a>, seller_wallet: AccountInfo<'a>, buyer_wallet: AccountInfo<'a>, multisig_wallet: AccountInfo<'a>, remaining_accounts: &[AccountInfo<'a>], used_reflink: Pubkey, rate: u8, fees: FeeSchedule, escrow_seeds: &[&[&[u8]]]) -> Result<()>{
+    if rate == 95{
+        let bal = escrow_account.lamports();
+        let mut residual_amt = fees.platform_fee(bal);
+        if  (used_reflink != Pubkey::from([0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0])) &&
+            (remaining_accounts.first().ok_or(PhysicalMarketErrors::MissingReflinkAccount)?.key() == used_reflink)
+        {
+            let reflink_amt = fees.reflink_share(bal);
+            residual_amt -= 2 * reflink_amt;
+            orbit_transaction::close_escrow_sol_flat!(
+                escrow_account.clone(),
+                buyer_wallet,
+                escrow_seeds,
+                reflink_amt
+            ).expect("couldnt close escrow");
             
-            market_accounts::cpi::decrement_dispute_discounts(
-                CpiContext::new_with_signer(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_market_account.to_account_info(),
-                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                        caller: ctx.accounts.physical_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
-            )?;
+            match orbit_transaction::remaining_accounts_to_wallet!(remaining_accounts){
+                Ok(reflink_wallet) => {
+                    orbit_transaction::close_escrow_sol_flat!(
+                        escrow_account.clone(),
+                        reflink_wallet.to_account_info(),
+                        escrow_seeds,
+                        reflink_amt
+                    ).expect("couldnt close escrow");
+                    reflink_wallet.exit(&crate::ID)?;
+                },
+                Err(e) => return Err(e)
+            }
+        }
+        orbit_transaction::close_escrow_sol_flat!(
+            escrow_account.clone(),
+            multisig_wallet,
+            escrow_seeds,
+            residual_amt
+        ).expect("couldnt close escrow");
+    };
+    
+    orbit_transaction::close_escrow_sol_rate!(
+        escrow_account,
+        seller_wallet,
+        escrow_seeds,
+        100
+    )
+}
 
-        }else{
-            ctx.accounts.physical_transaction.metadata.rate = 95
+/// pays out the platform fee (and reflink share) then sends the rest to the seller
+pub fn release_escrow_spl_helper<'a>(token_program: AccountInfo<'a>, escrow_account: AccountInfo<'a>, seller_token_account: AccountInfo<'a>, buyer_token_account: AccountInfo<'a>, multisig_ata: AccountInfo<'a>, program_auth: AccountInfo<'a>, remaining_accounts: &[AccountInfo<'a>], used_reflink: Pubkey, rate: u8, fees: FeeSchedule, auth_seeds: &[&[&[u8]]]) -> Result<()>{
+    let bal = amount(&escrow_account).expect("could not deserialize token account");
+    let mut seller_amt = bal;
+    if rate == 95{
+        let mut residual_amt = fees.platform_fee(bal);
+        seller_amt -= residual_amt;
+        if  (used_reflink != Pubkey::from([0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0])) &&
+            (remaining_accounts.first().ok_or(PhysicalMarketErrors::MissingReflinkAccount)?.key() == used_reflink)
+        {
+            let reflink_amt = fees.reflink_share(bal);
+            residual_amt -= 2 * reflink_amt;
+            orbit_transaction::close_escrow_spl_flat!(
+                token_program.clone(),
+                escrow_account.clone(),
+                buyer_token_account,
+                program_auth.clone(),
+                auth_seeds,
+                reflink_amt
+            ).expect("couldnt close escrow");
+
+            match orbit_transaction::remaining_accounts_to_token_account!(remaining_accounts){
+                Ok(reflink_token_account) => {
+                    orbit_transaction::close_escrow_spl_flat!(
+                        token_program.clone(),
+                        escrow_account.clone(),
+                        reflink_token_account.to_account_info(),
+                        program_auth.clone(),
+                        auth_seeds,
+                        reflink_amt
+                    ).expect("couldnt close escrow");
+                    reflink_token_account.exit(&crate::ID)?;
+                },
+                Err(e) => return Err(e)
+            }
+            
         }
+        orbit_transaction::close_escrow_spl_flat!(
+            token_program.clone(),
+            escrow_account.clone(),
+            multisig_ata,
+            program_auth.clone(),
+            auth_seeds,
+            residual_amt
+        ).expect("couldnt close escrow");
+    }
 
-        ctx.accounts.physical_transaction.metadata.buyer = ctx.accounts.buyer_market_account.voter_id;
-        ctx.accounts.physical_transaction.metadata.seller = ctx.accounts.seller_market_account.voter_id;
-        ctx.accounts.physical_transaction.metadata.product = ctx.accounts.phys_product.metadata.index;
-        ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Opened;
-        ctx.accounts.physical_transaction.metadata.transaction_price = price;
-        ctx.accounts.physical_transaction.metadata.currency = System::id();
-        ctx.accounts.physical_transaction.metadata.funded = false;
-        ctx.accounts.physical_transaction.metadata.buyer_tx_index = buyer_index;
-        ctx.accounts.physical_transaction.metadata.seller_tx_index = seller_index;
-        ctx.accounts.physical_transaction.metadata.reviews = TransactionReviews{
-            buyer: false,
-            seller: false
-        };
+    orbit_transaction::close_escrow_spl_flat!(
+        token_program,
+        escrow_account,
+        seller_token_account,
+        program_auth,
+        auth_seeds,
+        seller_amt
+    )
+}
+
+/// price the buyer has to open at, before any dispute discount
+pub fn listed_price(unit_price: u64, quantity: u32, cart: &[CartItem]) -> Result<u64>{
+    match order_total(
+        std::iter::once((unit_price, quantity)).chain(cart.iter().map(|item| (item.unit_price, item.quantity)))
+    ){
+        Some(total) => Ok(total),
+        None => err!(PhysicalMarketErrors::PriceOverflow)
+    }
+}
 
-        orbit_product::cpi::update_product_quantity_internal(
+pub fn open_sol_quantity(ctx: Context<OpenPhysicalTransactionSol>, seller_index: u8, buyer_index: u8, mut price: u64, use_discount: bool, quantity: u32) -> Result<()>{
+    
+    let auth_bump: &u8;
+    if let Some(ab) = ctx.bumps.get("physical_auth"){
+        auth_bump = ab
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    if quantity == 0 || quantity > PhysicalTransaction::MAX_QUANTITY || quantity > ctx.accounts.phys_product.quantity{
+        return err!(PhysicalMarketErrors::InvalidQuantity)
+    };
+    if (ctx.accounts.phys_product.metadata.currency != System::id()) ||
+        (price != listed_price(ctx.accounts.phys_product.metadata.price, quantity, &ctx.accounts.physical_transaction.cart)?){
+        return err!(PhysicalMarketErrors::ListingPriceMismatch)
+    };
+
+    if let Some(coupon) = &mut ctx.accounts.coupon{
+        let coupon_discount = redeem_coupon(
+            coupon,
+            ctx.accounts.seller_market_account.voter_id,
+            System::id(),
+            ctx.accounts.phys_product.metadata.index,
+            listed_price(ctx.accounts.phys_product.metadata.price, quantity, &[])?,
+            price
+        )?;
+        price -= coupon_discount;
+        ctx.accounts.physical_transaction.coupon = Some(coupon.key());
+        ctx.accounts.physical_transaction.coupon_discount = coupon_discount;
+    };
+
+    if use_discount && ctx.accounts.buyer_market_account.dispute_discounts > 0{
+        ctx.accounts.physical_transaction.metadata.rate = 100;
+        price = ctx.accounts.fee_config.fees.discounted_price(price);
+        
+        market_accounts::cpi::decrement_dispute_discounts(
             CpiContext::new_with_signer(
-                ctx.accounts.product_program.to_account_info(),
-                orbit_product::cpi::accounts::UpdatePhysicalQuantityInternal{
-                    product: ctx.accounts.phys_product.to_account_info(),
-                    vendor_account: ctx.accounts.seller_market_account.to_account_info(),
-                    vendor_listings: ctx.accounts.seller_listings.to_account_info(),
+                ctx.accounts.market_account_program.to_account_info(),
+                market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                    market_account: ctx.accounts.buyer_market_account.to_account_info(),
                     caller_auth: ctx.accounts.physical_auth.to_account_info(),
                     caller: ctx.accounts.physical_program.to_account_info()
                 },
                 &[&[b"market_authority", &[*auth_bump]]]
-            ),
-            ctx.accounts.phys_product.quantity-1
+            )
         )?;
 
-        orbit_transaction::cpi::add_buyer_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    tx: ctx.accounts.physical_transaction.to_account_info(),
-                    buyer_account: ctx.accounts.buyer_market_account.to_account_info(),
-                    wallet: ctx.accounts.buyer_wallet.to_account_info(),
-                }
-            ),
-            buyer_index
-        )?;
-        orbit_transaction::cpi::add_seller_physical_transaction(
-            CpiContext::new_with_signer(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    tx: ctx.accounts.physical_transaction.to_account_info()
-                },
-                &[&[b"market_authority", &[*auth_bump]]]
-            ),
-            seller_index
-        )?;
-        Ok(())
+    }else{
+        ctx.accounts.physical_transaction.metadata.rate = 95
     }
 
-    fn open_spl(ctx: Context<OpenPhysicalTransactionSpl>, seller_index: u8, buyer_index: u8, mut price: u64, use_discount: bool) -> Result<()>{
-        let auth_bump: &u8;
-        if let Some(ab) = ctx.bumps.get("physical_auth"){
-            auth_bump = ab
-        }else{
-            return err!(PhysicalMarketErrors::InvalidAuthBump)
-        };
-        if use_discount && ctx.accounts.buyer_market_account.dispute_discounts > 0{
-            ctx.accounts.physical_transaction.metadata.rate = 100;
-            price = price * 95 / 100;
-            
-            market_accounts::cpi::decrement_dispute_discounts(
-                CpiContext::new_with_signer(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_market_account.to_account_info(),
-                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                        caller: ctx.accounts.physical_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
-            )?;
+    ctx.accounts.physical_transaction.metadata.buyer = ctx.accounts.buyer_market_account.voter_id;
+    ctx.accounts.physical_transaction.metadata.seller = ctx.accounts.seller_market_account.voter_id;
+    ctx.accounts.physical_transaction.metadata.product = ctx.accounts.phys_product.metadata.index;
+    ctx.accounts.physical_transaction.quantity = quantity;
+    ctx.accounts.physical_transaction.fees = ctx.accounts.fee_config.fees;
+    ctx.accounts.physical_transaction.seller_key_version = encryption_key_version(&ctx.accounts.seller_encryption_key);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Opened;
+    ctx.accounts.physical_transaction.metadata.transaction_price = price;
+    ctx.accounts.physical_transaction.metadata.currency = System::id();
+    ctx.accounts.physical_transaction.metadata.funded = false;
+    ctx.accounts.physical_transaction.metadata.buyer_tx_index = buyer_index;
+    ctx.accounts.physical_transaction.metadata.seller_tx_index = seller_index;
+    ctx.accounts.physical_transaction.metadata.reviews = TransactionReviews{
+        buyer: false,
+        seller: false
+    };
+
+    update_product_quantity_helper(
+        ctx.accounts.product_program.to_account_info(),
+        ctx.accounts.phys_product.to_account_info(),
+        ctx.accounts.seller_market_account.to_account_info(),
+        ctx.accounts.seller_listings.to_account_info(),
+        ctx.accounts.physical_auth.to_account_info(),
+        ctx.accounts.physical_program.to_account_info(),
+        &[&[b"market_authority", &[*auth_bump]]],
+        ctx.accounts.phys_product.quantity - quantity
+    )?;
+
+    orbit_transaction::cpi::add_buyer_physical_transaction(
+        CpiContext::new(
+            ctx.accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddBuyerPhysicalTransactions{
+                transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
+                tx: ctx.accounts.physical_transaction.to_account_info(),
+                buyer_account: ctx.accounts.buyer_market_account.to_account_info(),
+                wallet: ctx.accounts.buyer_wallet.to_account_info(),
+            }
+        ),
+        buyer_index
+    )?;
+    orbit_transaction::cpi::add_seller_physical_transaction(
+        CpiContext::new_with_signer(
+            ctx.accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddSellerPhysicalTransactions{
+                transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
+                tx: ctx.accounts.physical_transaction.to_account_info()
+            },
+            &[&[b"market_authority", &[*auth_bump]]]
+        ),
+        seller_index
+    )?;
+    Ok(())
+}
 
-        }else{
-            ctx.accounts.physical_transaction.metadata.rate = 95
-        };
+pub fn open_spl_quantity(ctx: Context<OpenPhysicalTransactionSpl>, seller_index: u8, buyer_index: u8, mut price: u64, use_discount: bool, quantity: u32) -> Result<()>{
+    let auth_bump: &u8;
+    if let Some(ab) = ctx.bumps.get("physical_auth"){
+        auth_bump = ab
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    if quantity == 0 || quantity > PhysicalTransaction::MAX_QUANTITY || quantity > ctx.accounts.phys_product.quantity{
+        return err!(PhysicalMarketErrors::InvalidQuantity)
+    };
+    if (ctx.accounts.phys_product.metadata.currency != ctx.accounts.token_mint.key()) ||
+        (price != listed_price(ctx.accounts.phys_product.metadata.price, quantity, &ctx.accounts.physical_transaction.cart)?){
+        return err!(PhysicalMarketErrors::ListingPriceMismatch)
+    };
+
+    if let Some(coupon) = &mut ctx.accounts.coupon{
+        let coupon_discount = redeem_coupon(
+            coupon,
+            ctx.accounts.seller_market_account.voter_id,
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.phys_product.metadata.index,
+            listed_price(ctx.accounts.phys_product.metadata.price, quantity, &[])?,
+            price
+        )?;
+        price -= coupon_discount;
+        ctx.accounts.physical_transaction.coupon = Some(coupon.key());
+        ctx.accounts.physical_transaction.coupon_discount = coupon_discount;
+    };
+    if use_discount && ctx.accounts.buyer_market_account.dispute_discounts > 0{
+        ctx.accounts.physical_transaction.metadata.rate = 100;
+        price = ctx.accounts.fee_config.fees.discounted_price(price);
         
-        ctx.accounts.physical_transaction.metadata.buyer = ctx.accounts.buyer_market_account.voter_id;
-        ctx.accounts.physical_transaction.metadata.seller = ctx.accounts.seller_market_account.voter_id;
-        ctx.accounts.physical_transaction.metadata.product = ctx.accounts.phys_product.metadata.index;
-        ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Opened;
-        ctx.accounts.physical_transaction.metadata.transaction_price = price;
-        ctx.accounts.physical_transaction.metadata.currency = ctx.accounts.token_mint.key();
-        ctx.accounts.physical_transaction.metadata.buyer_tx_index = buyer_index;
-        ctx.accounts.physical_transaction.metadata.seller_tx_index = seller_index;
-        ctx.accounts.physical_transaction.metadata.funded = false;
-        ctx.accounts.physical_transaction.metadata.reviews = TransactionReviews{
-            buyer: false,
-            seller: false
-        };
-
-        orbit_product::cpi::update_product_quantity_internal(
+        market_accounts::cpi::decrement_dispute_discounts(
             CpiContext::new_with_signer(
-                ctx.accounts.product_program.to_account_info(),
-                orbit_product::cpi::accounts::UpdatePhysicalQuantityInternal{
-                    product: ctx.accounts.phys_product.to_account_info(),
-                    vendor_account: ctx.accounts.seller_market_account.to_account_info(),
-                    vendor_listings: ctx.accounts.seller_listings.to_account_info(),
+                ctx.accounts.market_account_program.to_account_info(),
+                market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                    market_account: ctx.accounts.buyer_market_account.to_account_info(),
                     caller_auth: ctx.accounts.physical_auth.to_account_info(),
                     caller: ctx.accounts.physical_program.to_account_info()
                 },
                 &[&[b"market_authority", &[*auth_bump]]]
-            ),
-            ctx.accounts.phys_product.quantity-1
+            )
         )?;
 
-        orbit_transaction::cpi::add_buyer_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    tx: ctx.accounts.physical_transaction.to_account_info(),
-                    buyer_account: ctx.accounts.buyer_market_account.to_account_info(),
-                    wallet: ctx.accounts.buyer_wallet.to_account_info(),
-                }
-            ),
-            buyer_index
-        )?;
-        orbit_transaction::cpi::add_seller_physical_transaction(
-            CpiContext::new_with_signer(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::AddSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    tx: ctx.accounts.physical_transaction.to_account_info()
-                },
-                &[&[b"market_authority", &[*auth_bump]]]
-            ),
-            seller_index
-        )?;
-        
-        Ok(())
+    }else{
+        ctx.accounts.physical_transaction.metadata.rate = 95
+    };
+    
+    ctx.accounts.physical_transaction.metadata.buyer = ctx.accounts.buyer_market_account.voter_id;
+    ctx.accounts.physical_transaction.metadata.seller = ctx.accounts.seller_market_account.voter_id;
+    ctx.accounts.physical_transaction.metadata.product = ctx.accounts.phys_product.metadata.index;
+    ctx.accounts.physical_transaction.quantity = quantity;
+    ctx.accounts.physical_transaction.fees = ctx.accounts.fee_config.fees;
+    ctx.accounts.physical_transaction.seller_key_version = encryption_key_version(&ctx.accounts.seller_encryption_key);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Opened;
+    ctx.accounts.physical_transaction.metadata.transaction_price = price;
+    ctx.accounts.physical_transaction.metadata.currency = ctx.accounts.token_mint.key();
+    ctx.accounts.physical_transaction.metadata.buyer_tx_index = buyer_index;
+    ctx.accounts.physical_transaction.metadata.seller_tx_index = seller_index;
+    ctx.accounts.physical_transaction.metadata.funded = false;
+    ctx.accounts.physical_transaction.metadata.reviews = TransactionReviews{
+        buyer: false,
+        seller: false
+    };
+
+    update_product_quantity_helper(
+        ctx.accounts.product_program.to_account_info(),
+        ctx.accounts.phys_product.to_account_info(),
+        ctx.accounts.seller_market_account.to_account_info(),
+        ctx.accounts.seller_listings.to_account_info(),
+        ctx.accounts.physical_auth.to_account_info(),
+        ctx.accounts.physical_program.to_account_info(),
+        &[&[b"market_authority", &[*auth_bump]]],
+        ctx.accounts.phys_product.quantity - quantity
+    )?;
+
+    orbit_transaction::cpi::add_buyer_physical_transaction(
+        CpiContext::new(
+            ctx.accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddBuyerPhysicalTransactions{
+                transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
+                tx: ctx.accounts.physical_transaction.to_account_info(),
+                buyer_account: ctx.accounts.buyer_market_account.to_account_info(),
+                wallet: ctx.accounts.buyer_wallet.to_account_info(),
+            }
+        ),
+        buyer_index
+    )?;
+    orbit_transaction::cpi::add_seller_physical_transaction(
+        CpiContext::new_with_signer(
+            ctx.accounts.transaction_program.to_account_info(),
+            orbit_transaction::cpi::accounts::AddSellerPhysicalTransactions{
+                transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
+                tx: ctx.accounts.physical_transaction.to_account_info()
+            },
+            &[&[b"market_authority", &[*auth_bump]]]
+        ),
+        seller_index
+    )?;
+    
+    Ok(())
+}
+
+impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, OpenPhysicalTransactionSol<'a>, OpenPhysicalTransactionSpl<'b>, ClosePhysicalTransactionSol<'c>, ClosePhysicalTransactionSpl<'d>, FundEscrowSol<'e>, FundEscrowSpl<'f>, CloseTransactionAccount<'g>, SellerEarlyDeclineSol<'h>, SellerEarlyDeclineSpl<'i>> for PhysicalTransaction{
+    fn open_sol(ctx: Context<OpenPhysicalTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
+        open_sol_quantity(ctx, seller_index, buyer_index, price, use_discount, 1)
+    }
+
+    fn open_spl(ctx: Context<OpenPhysicalTransactionSpl>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
+        open_spl_quantity(ctx, seller_index, buyer_index, price, use_discount, 1)
     }
 
     fn close_sol(ctx: Context<'_, '_, '_, 'c, ClosePhysicalTransactionSol<'c>>) -> Result<()>{
+        ctx.accounts.physical_transaction.apply_fee_tier(&ctx.accounts.seller_account, &ctx.accounts.fee_config);
+
         let physical_tx = ctx.accounts.physical_transaction.key();
         let physical_seed = physical_tx.as_ref();
-        let buyer_tx_log = ctx.accounts.buyer_account.key();
+        let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
         let buyer_tx_log_seed = buyer_tx_log.as_ref();
 
         if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
-            if ctx.accounts.physical_transaction.metadata.rate == 95{
-                let bal = ctx.accounts.escrow_account.lamports();
-                let mut residual_amt = bal * 5/100;
-                if  (ctx.accounts.buyer_account.used_reflink != Pubkey::from([0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0])) &&
-                    (ctx.remaining_accounts[0].key() == ctx.accounts.buyer_account.used_reflink)
-                {
-                    let reflink_amt = bal * 25 / 10000;
-                    residual_amt = bal * 45/1000;
-                    orbit_transaction::close_escrow_sol_flat!(
-                        ctx.accounts.escrow_account.to_account_info(),
-                        ctx.accounts.buyer_wallet.to_account_info(),
-                        &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
-                        reflink_amt
-                    ).expect("couldnt close escrow");
-                    
-                    match orbit_transaction::remaining_accounts_to_wallet!(ctx.remaining_accounts){
-                        Ok(reflink_wallet) => {
-                            orbit_transaction::close_escrow_sol_flat!(
-                                ctx.accounts.escrow_account.to_account_info(),
-                                reflink_wallet.to_account_info(),
-                                &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
-                                reflink_amt
-                            ).expect("couldnt close escrow");
-                            reflink_wallet.exit(ctx.program_id)?;
-                        },
-                        Err(e) => return Err(e)
-                    }
-                }
-                orbit_transaction::close_escrow_sol_flat!(
-                    ctx.accounts.escrow_account.to_account_info(),
-                    ctx.accounts.multisig_wallet.to_account_info(),
-                    &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
-                    residual_amt
-                ).expect("couldnt close escrow");
-            };
-            
-            orbit_transaction::close_escrow_sol_rate!(
+            release_escrow_sol_helper(
                 ctx.accounts.escrow_account.to_account_info(),
                 ctx.accounts.seller_wallet.to_account_info(),
-                &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
-                100
+                ctx.accounts.buyer_wallet.to_account_info(),
+                ctx.accounts.multisig_wallet.to_account_info(),
+                ctx.remaining_accounts,
+                ctx.accounts.buyer_account.used_reflink,
+                ctx.accounts.physical_transaction.metadata.rate,
+                ctx.accounts.physical_transaction.fees,
+                &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]]
             )?;
         }else{
             return err!(PhysicalMarketErrors::InvalidEscrowBump)
         };
 
-        if let Some(auth_bump) = ctx.bumps.get("phys_auth"){
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
             orbit_transaction::post_tx_incrementing!(
                 ctx.accounts.market_account_program.to_account_info(),
                 ctx.accounts.buyer_account.to_account_info(),
@@ -315,93 +546,51 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
                 &[&[b"market_authority", &[*auth_bump]]]
             )?;
 
-            orbit_product::cpi::physical_increment_times_sold(
-                CpiContext::new_with_signer(
-                    ctx.accounts.product_program.to_account_info(),
-                    orbit_product::cpi::accounts::IncrementPhysicalSoldInternal{
-                        product: ctx.accounts.phys_product.to_account_info(),
-                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                        caller: ctx.accounts.physical_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
+            increment_times_sold_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.quantity
+            )?;
+
+            clear_transaction_logs_helper(
+                ctx.accounts.transaction_program.to_account_info(),
+                ctx.accounts.seller_transactions_log.to_account_info(),
+                ctx.accounts.buyer_transactions_log.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.metadata.seller_tx_index,
+                ctx.accounts.physical_transaction.metadata.buyer_tx_index
             )?;
         }else{
             return err!(PhysicalMarketErrors::InvalidAuthBump)
         };
-
-        orbit_transaction::cpi::clear_seller_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
         
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
         ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
         Ok(())
     }
 
     fn close_spl(ctx: Context<'_, '_, '_, 'd, ClosePhysicalTransactionSpl<'d>>) -> Result<()>{
-        if let Some(auth_bump) = ctx.bumps.get("phys_auth"){
-            if ctx.accounts.physical_transaction.metadata.rate == 95{
-                let bal = amount(&ctx.accounts.escrow_account.to_account_info()).expect("could not deserialize token account");
-                let mut residual_amt = bal * 5/100;
-                if  (ctx.accounts.buyer_account.used_reflink != Pubkey::from([0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0])) &&
-                    (ctx.remaining_accounts[0].key() == ctx.accounts.buyer_account.used_reflink)
-                {
-                    let reflink_amt = bal * 25 / 10000;
-                    residual_amt = bal * 45/1000;
-                    orbit_transaction::close_escrow_spl_flat!(
-                        ctx.accounts.token_program.to_account_info(),
-                        ctx.accounts.escrow_account.to_account_info(),
-                        ctx.accounts.buyer_token_account.to_account_info(),
-                        ctx.accounts.physical_auth.to_account_info(),
-                        &[&[b"market_authority", &[*auth_bump]]],
-                        reflink_amt
-                    ).expect("couldnt close escrow");
+        ctx.accounts.physical_transaction.apply_fee_tier(&ctx.accounts.seller_account, &ctx.accounts.fee_config);
 
-                    match orbit_transaction::remaining_accounts_to_token_account!(ctx.remaining_accounts){
-                        Ok(reflink_token_account) => {
-                            orbit_transaction::close_escrow_spl_flat!(
-                                ctx.accounts.token_program.to_account_info(),
-                                ctx.accounts.escrow_account.to_account_info(),
-                                reflink_token_account.to_account_info(),
-                                ctx.accounts.physical_auth.to_account_info(),
-                                &[&[b"market_authority", &[*auth_bump]]],
-                                reflink_amt
-                            ).expect("couldnt close escrow");
-                            reflink_token_account.exit(ctx.program_id)?;
-                        },
-                        Err(e) => return Err(e)
-                    }
-                    
-                }
-                orbit_transaction::close_escrow_spl_flat!(
-                    ctx.accounts.token_program.to_account_info(),
-                    ctx.accounts.escrow_account.to_account_info(),
-                    ctx.accounts.multisig_ata.to_account_info(),
-                    ctx.accounts.physical_auth.to_account_info(),
-                    &[&[b"market_authority", &[*auth_bump]]],
-                    residual_amt
-                ).expect("couldnt close escrow");
-            }
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+            release_escrow_spl_helper(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.seller_token_account.to_account_info(),
+                ctx.accounts.buyer_token_account.to_account_info(),
+                ctx.accounts.multisig_ata.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.remaining_accounts,
+                ctx.accounts.buyer_account.used_reflink,
+                ctx.accounts.physical_transaction.metadata.rate,
+                ctx.accounts.physical_transaction.fees,
+                &[&[b"market_authority", &[*auth_bump]]]
+            )?;
             
             orbit_transaction::post_tx_incrementing!(
                 ctx.accounts.market_account_program.to_account_info(),
@@ -411,55 +600,30 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
                 ctx.accounts.physical_program.to_account_info(),
                 &[&[b"market_authority", &[*auth_bump]]]
             )?;
-            orbit_product::cpi::physical_increment_times_sold(
-                CpiContext::new_with_signer(
-                    ctx.accounts.product_program.to_account_info(),
-                    orbit_product::cpi::accounts::IncrementPhysicalSoldInternal{
-                        product: ctx.accounts.phys_product.to_account_info(),
-                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                        caller: ctx.accounts.physical_program.to_account_info()
-                    },
-                    &[&[b"market_authority", &[*auth_bump]]]
-                )
+            increment_times_sold_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.quantity
             )?;
-            
-            orbit_transaction::close_escrow_spl_rate!(
-                ctx.accounts.token_program.to_account_info(),
-                ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.seller_token_account.to_account_info(),
+
+            clear_transaction_logs_helper(
+                ctx.accounts.transaction_program.to_account_info(),
+                ctx.accounts.seller_transactions_log.to_account_info(),
+                ctx.accounts.buyer_transactions_log.to_account_info(),
                 ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
                 &[&[b"market_authority", &[*auth_bump]]],
-                ctx.accounts.physical_transaction.metadata.transaction_price,
-                100
-            )
+                ctx.accounts.physical_transaction.metadata.seller_tx_index,
+                ctx.accounts.physical_transaction.metadata.buyer_tx_index
+            )?;
         }else{
             return err!(PhysicalMarketErrors::InvalidAuthBump)
-        }?;
-
-        orbit_transaction::cpi::clear_seller_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
+        };
 
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
         ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
         Ok(())
     }
@@ -476,6 +640,11 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
                 ctx.accounts.escrow_account.to_account_info()
             ]
         ).expect("could not fund escrow");
+        let handling_time = match ctx.accounts.physical_transaction.handling_time{
+            Some(declared) => declared,
+            None => ctx.accounts.market_config.default_handling_time
+        };
+        ctx.accounts.physical_transaction.ship_by = Clock::get()?.unix_timestamp.saturating_add(handling_time);
         ctx.accounts.physical_transaction.metadata.funded = true;
         ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::BuyerFunded;
         Ok(())
@@ -493,6 +662,11 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
             ),
             ctx.accounts.physical_transaction.metadata.transaction_price
         ).expect("could not fund escrow account. maybe check your balance");
+        let handling_time = match ctx.accounts.physical_transaction.handling_time{
+            Some(declared) => declared,
+            None => ctx.accounts.market_config.default_handling_time
+        };
+        ctx.accounts.physical_transaction.ship_by = Clock::get()?.unix_timestamp.saturating_add(handling_time);
         ctx.accounts.physical_transaction.metadata.funded = true;
         ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::BuyerFunded;
         Ok(())
@@ -503,23 +677,41 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
     }
     
     fn seller_early_decline_sol(ctx: Context<SellerEarlyDeclineSol>) -> Result<()>{
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+        restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
         ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
-        if ctx.accounts.physical_transaction.metadata.rate == 100{
-            market_accounts::cpi::increment_dispute_discounts(
-                CpiContext::new(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_account.to_account_info(),
-                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                        caller: ctx.accounts.physical_program.to_account_info()
-                    }
-                )
-            )?;
-        };
-        
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+            if ctx.accounts.physical_transaction.metadata.rate == 100{
+                market_accounts::cpi::increment_dispute_discounts(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.market_account_program.to_account_info(),
+                        market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                            market_account: ctx.accounts.buyer_account.to_account_info(),
+                            caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                            caller: ctx.accounts.physical_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+            };
+
+            update_product_quantity_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.seller_listings.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+            )?;
+        }else{
+            return err!(PhysicalMarketErrors::InvalidAuthBump)
+        };
+        
         let physical_tx = ctx.accounts.physical_transaction.key();
         let physical_seed = physical_tx.as_ref();
-        let buyer_tx_log = ctx.accounts.buyer_account.key();
+        let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
         let buyer_tx_log_seed = buyer_tx_log.as_ref();
 
         if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
@@ -533,49 +725,58 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
             return err!(PhysicalMarketErrors::InvalidEscrowBump)
         };
         
-        orbit_transaction::cpi::clear_seller_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_physical_transaction(
-            CpiContext::new(
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+            clear_transaction_logs_helper(
                 ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
+                ctx.accounts.seller_transactions_log.to_account_info(),
+                ctx.accounts.buyer_transactions_log.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.metadata.seller_tx_index,
+                ctx.accounts.physical_transaction.metadata.buyer_tx_index
+            )?;
+        }else{
+            return err!(PhysicalMarketErrors::InvalidAuthBump)
+        };
         Ok(())
     }
 
     fn seller_early_decline_spl(ctx: Context<SellerEarlyDeclineSpl>) -> Result<()>{
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+        restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
         ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
 
-        if ctx.accounts.physical_transaction.metadata.rate == 100{
-            market_accounts::cpi::increment_dispute_discounts(
-                CpiContext::new(
-                    ctx.accounts.market_account_program.to_account_info(),
-                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
-                        market_account: ctx.accounts.buyer_market_account.to_account_info(),
-                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                        caller: ctx.accounts.physical_program.to_account_info()
-                    }
-                )
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+            if ctx.accounts.physical_transaction.metadata.rate == 100{
+                market_accounts::cpi::increment_dispute_discounts(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.market_account_program.to_account_info(),
+                        market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                            market_account: ctx.accounts.buyer_market_account.to_account_info(),
+                            caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                            caller: ctx.accounts.physical_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+            };
+
+            update_product_quantity_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.seller_listings.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
             )?;
+        }else{
+            return err!(PhysicalMarketErrors::InvalidAuthBump)
         }
 
-        if let Some(auth_bump) = ctx.bumps.get("phys_auth"){
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
             orbit_transaction::close_escrow_spl_rate!(
                 ctx.accounts.token_program.to_account_info(),
                 ctx.accounts.escrow_account.to_account_info(),
@@ -589,29 +790,20 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i> OrbitTransactionTrait<'a, 'b, 'c, 'd, '
             return err!(PhysicalMarketErrors::InvalidAuthBump)
         }?;    
         
-        orbit_transaction::cpi::clear_seller_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
-
-        orbit_transaction::cpi::clear_buyer_physical_transaction(
-            CpiContext::new(
+        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+            clear_transaction_logs_helper(
                 ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
+                ctx.accounts.seller_transactions_log.to_account_info(),
+                ctx.accounts.buyer_transactions_log.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.metadata.seller_tx_index,
+                ctx.accounts.physical_transaction.metadata.buyer_tx_index
+            )?;
+        }else{
+            return err!(PhysicalMarketErrors::InvalidAuthBump)
+        };
 
         Ok(())
 
@@ -652,6 +844,25 @@ pub struct OpenPhysicalDispute<'info>{
     )]
     pub opener_wallet: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_bond",
+            physical_transaction.key().as_ref(),
+            &dispute_bond.opener.to_le_bytes()
+        ],
+        bump,
+        constraint = dispute_bond.opener_wallet == opener_wallet.key(),
+        constraint = !dispute_bond.used
+    )]
+    pub dispute_bond: Box<Account<'info, DisputeBond>>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
     ////////////////////////////////////////////
     /// BUYER SELLER
     
@@ -702,7 +913,16 @@ impl<'a, 'b, 'c> OrbitDisputableTrait<'a, 'b, 'c, OpenPhysicalDispute<'a>, Close
             return err!(PhysicalMarketErrors::DisputeExists)
         }
 
-        ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Frozen;
+        let now = Clock::get()?.unix_timestamp;
+        if (ctx.accounts.physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery) &&
+            !dispute_window_open(now, ctx.accounts.physical_transaction.delivered_at, ctx.accounts.market_config.dispute_window)
+        {
+            return err!(PhysicalMarketErrors::DisputeWindowClosed)
+        };
+
+        ctx.accounts.physical_transaction.frozen_at = now;
+        ctx.accounts.physical_transaction.transition(TransactionState::Frozen);
+        ctx.accounts.dispute_bond.used = true;
 
         if let Some(signer_bump) = ctx.bumps.get("physical_auth"){
             orbit_dispute::cpi::open_dispute(
@@ -718,7 +938,7 @@ impl<'a, 'b, 'c> OrbitDisputableTrait<'a, 'b, 'c, OpenPhysicalDispute<'a>, Close
                         payer: ctx.accounts.opener_wallet.to_account_info(),
                         system_program: ctx.accounts.system_program.to_account_info()
                     },
-                    &[&[b"physical_auth", &[*signer_bump]]]
+                    &[&[b"market_authority", &[*signer_bump]]]
                 ),
                 threshold
             ) 
@@ -730,37 +950,138 @@ impl<'a, 'b, 'c> OrbitDisputableTrait<'a, 'b, 'c, OpenPhysicalDispute<'a>, Close
     }
     
     fn close_dispute_sol(ctx: Context<ClosePhysicalDisputeSol>) -> Result<()>{
+        settle_dispute_sol(ctx, false)
+    }
 
-        let physical_tx = ctx.accounts.physical_transaction.key();
-        let physical_seed = physical_tx.as_ref();
-        let buyer_tx_log = ctx.accounts.buyer_account.key();
-        let buyer_tx_log_seed = buyer_tx_log.as_ref();
+    fn close_dispute_spl(ctx: Context<ClosePhysicalDisputeSpl>) -> Result<()>{
+        settle_dispute_spl(ctx, false)
+    }
+}
 
-        if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
-            if ctx.accounts.physical_transaction.metadata.rate == 95{
-                orbit_transaction::close_escrow_sol_rate!(
+/// jurors resolved it, or nobody did in time and the configured default split applies
+pub fn settle_dispute_sol(ctx: Context<ClosePhysicalDisputeSol>, default_judgment: bool) -> Result<()>{
+    let resolved = ctx.accounts.phys_dispute.dispute_state == DisputeState::Resolved;
+    if default_judgment{
+        if resolved || !default_judgment_due(Clock::get()?.unix_timestamp, ctx.accounts.physical_transaction.frozen_at, ctx.accounts.market_config.resolution_window){
+            return err!(PhysicalMarketErrors::DisputeNotResolved)
+        };
+    }else
+    if !resolved{
+        return err!(PhysicalMarketErrors::DisputeNotResolved)
+    }else
+    if (ctx.accounts.market_config.appeal_window > 0) && !ctx.accounts.physical_transaction.appealed{
+        // first ruling has to be recorded and sit out the appeal window
+        if (ctx.accounts.physical_transaction.ruling_at == 0) ||
+            appeal_window_open(Clock::get()?.unix_timestamp, ctx.accounts.physical_transaction.ruling_at, ctx.accounts.market_config.appeal_window)
+        {
+            return err!(PhysicalMarketErrors::AppealWindowOpen)
+        };
+    };
+
+    let buyer_bps = if default_judgment{
+        ctx.accounts.market_config.default_buyer_bps
+    }else{
+        resolution_buyer_bps(
+            &ctx.accounts.dispute_resolution,
+            ctx.accounts.favor_market_account.voter_id == ctx.accounts.physical_transaction.metadata.buyer
+        )
+    };
+
+    let physical_tx = ctx.accounts.physical_transaction.key();
+    let physical_seed = physical_tx.as_ref();
+    let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_tx_log.as_ref();
+
+    if let Some(escrow_bump) = ctx.bumps.get("escrow_account"){
+        let split = dispute_split(
+            ctx.accounts.escrow_account.lamports(),
+            buyer_bps,
+            ctx.accounts.physical_transaction.metadata.rate,
+            &ctx.accounts.physical_transaction.fees
+        );
+
+        if split.seller + split.fee > 0{
+            if split.buyer > 0{
+                orbit_transaction::close_escrow_sol_flat!(
+                    ctx.accounts.escrow_account.to_account_info(),
+                    ctx.accounts.buyer_wallet.to_account_info(),
+                    &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                    split.buyer
+                ).expect("couldnt refund buyer");
+            };
+            if split.fee > 0{
+                orbit_transaction::close_escrow_sol_flat!(
                     ctx.accounts.escrow_account.to_account_info(),
                     ctx.accounts.multisig_wallet.to_account_info(),
                     &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_bump]]],
-                    100 - ctx.accounts.physical_transaction.metadata.rate
+                    split.fee
                 ).expect("couldnt close escrow");
-            }
+            };
             orbit_transaction::close_escrow_sol_rate!(
                 ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.favor_wallet.to_account_info(),
+                ctx.accounts.seller_wallet.to_account_info(),
                 &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_bump]]],
                 100
             )
         }else{
-            return err!(PhysicalMarketErrors::InvalidEscrowBump)
-        }?;
-        
-        if ctx.accounts.physical_transaction.metadata.rate == 100 && ctx.accounts.favor_market_account.voter_id == ctx.accounts.physical_transaction.metadata.buyer {
-            // todo: whyd i fucking do this
-            // ctx.accounts.favor_market_account.dispute_discounts += 1;
+            orbit_transaction::close_escrow_sol_rate!(
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.buyer_wallet.to_account_info(),
+                &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_bump]]],
+                100
+            )
         }
+    }else{
+        return err!(PhysicalMarketErrors::InvalidEscrowBump)
+    }?;
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        if buyer_bps == 10000{
+            if ctx.accounts.physical_transaction.metadata.rate == 100{
+                market_accounts::cpi::increment_dispute_discounts(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.market_accounts_program.to_account_info(),
+                        market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                            market_account: ctx.accounts.buyer_account.to_account_info(),
+                            caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                            caller: ctx.accounts.physical_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+            };
+            update_product_quantity_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.seller_listings.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+            )?;
+        }else{
+            // seller got paid something, so it counts as a completed sale for both sides
+            orbit_transaction::post_tx_incrementing!(
+                ctx.accounts.market_accounts_program.to_account_info(),
+                ctx.accounts.buyer_account.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]]
+            )?;
+            increment_times_sold_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.quantity
+            )?;
+        };
 
-        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        // orbit_dispute only closes resolved disputes, a defaulted one is marked and closed once it resolves
+        if !default_judgment{
             close_dispute_helper(
                 ctx.accounts.dispute_program.to_account_info(),
                 ctx.accounts.phys_dispute.to_account_info(),
@@ -768,68 +1089,149 @@ impl<'a, 'b, 'c> OrbitDisputableTrait<'a, 'b, 'c, OpenPhysicalDispute<'a>, Close
                 ctx.accounts.physical_auth.to_account_info(),
                 ctx.accounts.physical_program.to_account_info(),
                 &[&[b"market_authority", &[*auth_bump]]]
-            )
-        }else{
-            return err!(PhysicalMarketErrors::InvalidAuthBump)
-        }?;
-
-        orbit_transaction::cpi::clear_seller_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
+            )?;
+        };
 
-        orbit_transaction::cpi::clear_buyer_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
         )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.dispute_buyer_bps = Some(buyer_bps);
+    ctx.accounts.physical_transaction.dispute_defaulted = default_judgment;
+    if buyer_bps == 10000{
+        restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    }else{
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
+    };
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
 
-        ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
-        Ok(())
-    }
-
-    fn close_dispute_spl(ctx: Context<ClosePhysicalDisputeSpl>) -> Result<()>{
-        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
-            orbit_transaction::close_escrow_spl_rate!(
+/// jurors resolved it, or nobody did in time and the configured default split applies
+pub fn settle_dispute_spl(ctx: Context<ClosePhysicalDisputeSpl>, default_judgment: bool) -> Result<()>{
+    let resolved = ctx.accounts.phys_dispute.dispute_state == DisputeState::Resolved;
+    if default_judgment{
+        if resolved || !default_judgment_due(Clock::get()?.unix_timestamp, ctx.accounts.physical_transaction.frozen_at, ctx.accounts.market_config.resolution_window){
+            return err!(PhysicalMarketErrors::DisputeNotResolved)
+        };
+    }else
+    if !resolved{
+        return err!(PhysicalMarketErrors::DisputeNotResolved)
+    }else
+    if (ctx.accounts.market_config.appeal_window > 0) && !ctx.accounts.physical_transaction.appealed{
+        // first ruling has to be recorded and sit out the appeal window
+        if (ctx.accounts.physical_transaction.ruling_at == 0) ||
+            appeal_window_open(Clock::get()?.unix_timestamp, ctx.accounts.physical_transaction.ruling_at, ctx.accounts.market_config.appeal_window)
+        {
+            return err!(PhysicalMarketErrors::AppealWindowOpen)
+        };
+    };
+
+    let buyer_bps = if default_judgment{
+        ctx.accounts.market_config.default_buyer_bps
+    }else{
+        resolution_buyer_bps(
+            &ctx.accounts.dispute_resolution,
+            ctx.accounts.favor_market_account.voter_id == ctx.accounts.physical_transaction.metadata.buyer
+        )
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        let split = dispute_split(
+            ctx.accounts.escrow_account.amount,
+            buyer_bps,
+            ctx.accounts.physical_transaction.metadata.rate,
+            &ctx.accounts.physical_transaction.fees
+        );
+
+        if split.buyer > 0{
+            orbit_transaction::close_escrow_spl_flat!(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.buyer_token_account.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                split.buyer
+            ).expect("couldnt refund buyer");
+        };
+        if split.fee > 0{
+            orbit_transaction::close_escrow_spl_flat!(
                 ctx.accounts.token_program.to_account_info(),
                 ctx.accounts.escrow_account.to_account_info(),
                 ctx.accounts.multisig_ata.to_account_info(),
                 ctx.accounts.physical_auth.to_account_info(),
                 &[&[b"market_authority", &[*auth_bump]]],
-                ctx.accounts.physical_transaction.metadata.transaction_price,
-                100-ctx.accounts.physical_transaction.metadata.rate
+                split.fee
             ).expect("couldnt close dispute escrow");
-            orbit_transaction::close_escrow_spl_rate!(
+        };
+        if split.seller > 0{
+            orbit_transaction::close_escrow_spl_flat!(
                 ctx.accounts.token_program.to_account_info(),
                 ctx.accounts.escrow_account.to_account_info(),
-                ctx.accounts.favor_token_account.to_account_info(),
+                ctx.accounts.seller_token_account.to_account_info(),
                 ctx.accounts.physical_auth.to_account_info(),
                 &[&[b"market_authority", &[*auth_bump]]],
-                ctx.accounts.physical_transaction.metadata.transaction_price,
-                100
-            )
-        }else{
-            return err!(PhysicalMarketErrors::InvalidEscrowBump)
-        }?;
+                split.seller
+            ).expect("couldnt pay seller");
+        };
 
-        if ctx.accounts.physical_transaction.metadata.rate == 100 && ctx.accounts.favor_market_account.voter_id == ctx.accounts.physical_transaction.metadata.buyer {
-            // ctx.accounts.favor_market_account.dispute_discounts += 1;
-        }
+        if buyer_bps == 10000{
+            if ctx.accounts.physical_transaction.metadata.rate == 100{
+                market_accounts::cpi::increment_dispute_discounts(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.market_accounts_program.to_account_info(),
+                        market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                            market_account: ctx.accounts.buyer_market_account.to_account_info(),
+                            caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                            caller: ctx.accounts.physical_program.to_account_info()
+                        },
+                        &[&[b"market_authority", &[*auth_bump]]]
+                    )
+                )?;
+            };
+            update_product_quantity_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.seller_listings.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+            )?;
+        }else{
+            // seller got paid something, so it counts as a completed sale for both sides
+            orbit_transaction::post_tx_incrementing!(
+                ctx.accounts.market_accounts_program.to_account_info(),
+                ctx.accounts.buyer_market_account.to_account_info(),
+                ctx.accounts.seller_account.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]]
+            )?;
+            increment_times_sold_helper(
+                ctx.accounts.product_program.to_account_info(),
+                ctx.accounts.phys_product.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                ctx.accounts.physical_program.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                ctx.accounts.physical_transaction.quantity
+            )?;
+        };
 
-        if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        // orbit_dispute only closes resolved disputes, a defaulted one is marked and closed once it resolves
+        if !default_judgment{
             close_dispute_helper(
                 ctx.accounts.dispute_program.to_account_info(),
                 ctx.accounts.phys_dispute.to_account_info(),
@@ -837,59 +1239,145 @@ impl<'a, 'b, 'c> OrbitDisputableTrait<'a, 'b, 'c, OpenPhysicalDispute<'a>, Close
                 ctx.accounts.physical_auth.to_account_info(),
                 ctx.accounts.physical_program.to_account_info(),
                 &[&[b"market_authority", &[*auth_bump]]]
-            )
-        }else{
-            return err!(PhysicalMarketErrors::InvalidAuthBump)
-        }?;
-
-        orbit_transaction::cpi::clear_seller_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearSellerPhysicalTransactions{
-                    transactions_log: ctx.accounts.seller_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
-        )?;
+            )?;
+        };
 
-        orbit_transaction::cpi::clear_buyer_physical_transaction(
-            CpiContext::new(
-                ctx.accounts.transaction_program.to_account_info(),
-                orbit_transaction::cpi::accounts::ClearBuyerPhysicalTransactions{
-                    transactions_log: ctx.accounts.buyer_transactions_log.to_account_info(),
-                    caller_auth: ctx.accounts.physical_auth.to_account_info(),
-                    caller: ctx.accounts.physical_program.to_account_info()
-                }
-            ),
-            ctx.accounts.physical_transaction.metadata.seller_tx_index
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
         )?;
-
-        ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
-        Ok(())
-    }
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.dispute_buyer_bps = Some(buyer_bps);
+    ctx.accounts.physical_transaction.dispute_defaulted = default_judgment;
+    if buyer_bps == 10000{
+        restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    }else{
+        ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
+    };
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
 }
 
 ///////////////////////////////////////////////////////////////////////////////////////
-/// SELLER CONFIRMATIONS
+/// DEFAULTED DISPUTES
+/// default judgment settles the tx while the orbit_dispute is still open. once jurors
+/// finally resolve it anyone can close it, the ruling has no effect on the tx anymore
 
 #[derive(Accounts)]
-pub struct SellerConfirmationsContext<'info>{
+pub struct CloseDefaultedDispute<'info>{
     #[account(
         mut,
-        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed,
+        constraint = physical_transaction.dispute_defaulted
     )]
-    pub physical_transaction: Account<'info, PhysicalTransaction>,
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
 
     #[account(
-        constraint = seller_market_account.voter_id == physical_transaction.metadata.seller
+        mut,
+        constraint = phys_dispute.dispute_transaction == physical_transaction.key(),
+        constraint = phys_dispute.dispute_state == DisputeState::Resolved,
+        has_one = funder
     )]
-    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
 
     #[account(
-        mut,
-        seeds = [
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+
+    pub dispute_program: Program<'info, Dispute>,
+}
+
+pub fn close_defaulted_dispute(ctx: Context<CloseDefaultedDispute>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        close_dispute_helper(
+            ctx.accounts.dispute_program.to_account_info(),
+            ctx.accounts.phys_dispute.to_account_info(),
+            ctx.accounts.funder.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+    ctx.accounts.physical_transaction.dispute_defaulted = false;
+    Ok(())
+}
+
+///////////////////////////////////////////////////////////////////////////////////////
+/// SELLER CONFIRMATIONS
+
+#[derive(Accounts)]
+pub struct SellerAcceptTransaction<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Opened
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        address = seller_market_account.wallet
+    )]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+}
+
+pub fn seller_accept_transaction(ctx: Context<SellerAcceptTransaction>, seller_note: Option<[u8; 64]>, estimated_ship_date: Option<i64>, handling_time: Option<i64>) -> Result<()>{
+    if let Some(declared) = handling_time{
+        if (declared <= 0) || (declared > ctx.accounts.market_config.max_handling_time){
+            return err!(PhysicalMarketErrors::InvalidHandlingTime)
+        };
+    };
+    ctx.accounts.physical_transaction.seller_note = seller_note;
+    ctx.accounts.physical_transaction.estimated_ship_date = estimated_ship_date;
+    ctx.accounts.physical_transaction.handling_time = handling_time;
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::SellerConfirmed;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SellerConfirmationsContext<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
             b"seller_transactions",
             (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
             &seller_market_account.voter_id.to_le_bytes()
@@ -905,9 +1393,55 @@ pub struct SellerConfirmationsContext<'info>{
     pub wallet: Signer<'info>,
 }
 
-pub fn update_shipping(ctx: Context<SellerConfirmationsContext>, enc_shipping: [u8; 64]) -> Result<()>{
-    ctx.accounts.physical_transaction.shipping = enc_shipping;
-    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Shipped;
+/// tracking_commitment is sha256(salt || tracking number). the number stays private unless revealed in a dispute
+pub fn update_shipping(ctx: Context<SellerConfirmationsContext>, carrier: Carrier, tracking_commitment: [u8; 32]) -> Result<()>{
+    if let Carrier::Other(name) = carrier{
+        if name == [0; 16]{
+            return err!(PhysicalMarketErrors::InvalidShipment)
+        }
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.physical_transaction.shipment = Some(ShipmentRecord{
+        carrier,
+        tracking_commitment,
+        shipped_at: now,
+        revealed: false
+    });
+    ctx.accounts.physical_transaction.shipped_at = now;
+    ctx.accounts.physical_transaction.transition(TransactionState::Shipped);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevealTracking<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen,
+        constraint = physical_transaction.shipment.is_some()
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        address = seller_market_account.wallet
+    )]
+    pub wallet: Signer<'info>,
+}
+
+/// opens the tracking commitment for jurors. the number is logged, the tx only keeps that it matched
+pub fn reveal_tracking(ctx: Context<RevealTracking>, tracking_number: Vec<u8>, salt: [u8; 32]) -> Result<()>{
+    if let Some(shipment) = &mut ctx.accounts.physical_transaction.shipment{
+        if hashv(&[&salt, &tracking_number]).to_bytes() != shipment.tracking_commitment{
+            return err!(PhysicalMarketErrors::TrackingMismatch)
+        };
+        shipment.revealed = true;
+        msg!("tracking number: {}", String::from_utf8_lossy(&tracking_number));
+    };
     Ok(())
 }
 
@@ -919,12 +1453,15 @@ pub fn update_shipping(ctx: Context<SellerConfirmationsContext>, enc_shipping: [
 pub struct BuyerConfirm<'info>{
     #[account(
         mut,
-        constraint = physical_transaction.metadata.transaction_state == TransactionState::Shipped
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery)
     )]
     pub physical_transaction: Account<'info, PhysicalTransaction>,
 
     #[account(
-        mut
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
     )]
     pub buyer_account: Account<'info, OrbitMarketAccount>,
 
@@ -946,7 +1483,11 @@ pub struct BuyerConfirm<'info>{
 }
 
 pub fn confirm_delivery(ctx: Context<BuyerConfirm>) -> Result<()>{
-    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedDelivery;
+    if ctx.accounts.physical_transaction.metadata.transaction_state != TransactionState::Shipped{
+        return err!(PhysicalMarketErrors::NotShipped);
+    }
+    ctx.accounts.physical_transaction.delivered_at = Clock::get()?.unix_timestamp;
+    ctx.accounts.physical_transaction.transition(TransactionState::BuyerConfirmedDelivery);
     Ok(())
 }
 
@@ -954,7 +1495,772 @@ pub fn confirm_product(ctx: Context<BuyerConfirm>) -> Result<()>{
     if ctx.accounts.physical_transaction.metadata.transaction_state != TransactionState::BuyerConfirmedDelivery{
         return err!(PhysicalMarketErrors::DidNotConfirmDelivery);
     }
-    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::BuyerConfirmedProduct;
+    if (ctx.accounts.physical_transaction.return_state != ReturnState::None) &&
+        (ctx.accounts.physical_transaction.return_state != ReturnState::Rejected)
+    {
+        return err!(PhysicalMarketErrors::InvalidReturnState);
+    }
+    ctx.accounts.physical_transaction.transition(TransactionState::BuyerConfirmedProduct);
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// GIFT ORDERS
+/// buyer still pays, gets refunds and reviews. the recipient only receives and confirms delivery
+
+#[derive(Accounts)]
+pub struct SetGiftRecipient<'info>{
+    #[account(
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Opened) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded)
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+}
+
+pub fn set_gift_recipient(ctx: Context<SetGiftRecipient>, recipient: Option<Pubkey>) -> Result<()>{
+    // the envelope is sealed to whoever was the recipient at the time
+    if ctx.accounts.physical_transaction.shipping.scheme != 0{
+        return err!(PhysicalMarketErrors::ShippingAlreadySealed)
+    };
+    ctx.accounts.physical_transaction.gift_recipient = recipient;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecipientConfirm<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Shipped,
+        constraint = physical_transaction.gift_recipient == Some(recipient.key())
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    pub recipient: Signer<'info>,
+}
+
+pub fn recipient_confirm_delivery(ctx: Context<RecipientConfirm>) -> Result<()>{
+    ctx.accounts.physical_transaction.delivered_at = Clock::get()?.unix_timestamp;
+    ctx.accounts.physical_transaction.transition(TransactionState::BuyerConfirmedDelivery);
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// SHIPPING ADDRESS
+/// buyer seals the address to the sellers published key (or the gift recipients) before it ships
+
+#[derive(Accounts)]
+#[instruction(enc_shipping: ShippingEnvelope)]
+pub struct SetShippingAddress<'info>{
+    #[account(
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Opened) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded),
+        realloc = PhysicalTransaction::space_with(&enc_shipping, &physical_transaction.return_shipping),
+        realloc::payer = buyer_wallet,
+        realloc::zero = false
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+pub fn set_shipping_address(ctx: Context<SetShippingAddress>, enc_shipping: ShippingEnvelope) -> Result<()>{
+    if !enc_shipping.is_valid(){
+        return err!(PhysicalMarketErrors::InvalidShippingEnvelope)
+    };
+    ctx.accounts.physical_transaction.shipping = enc_shipping;
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// DELIVERY ATTESTATIONS
+/// the registered oracle signs off that the carrier delivered the committed tracking number.
+/// it has to present the opening of the commitment, so that tracking number ends up public
+
+#[derive(Accounts)]
+pub struct AttestDelivery<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Shipped,
+        constraint = physical_transaction.shipment.is_some()
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        seeds = [b"delivery_oracle"],
+        bump
+    )]
+    pub delivery_oracle: Account<'info, DeliveryOracle>,
+
+    #[account(
+        address = delivery_oracle.signer
+    )]
+    pub oracle_signer: Signer<'info>,
+}
+
+pub fn attest_delivery(ctx: Context<AttestDelivery>, tracking_number: Vec<u8>, salt: [u8; 32], delivered_at: i64) -> Result<()>{
+    if let Some(shipment) = &mut ctx.accounts.physical_transaction.shipment{
+        if hashv(&[&salt, &tracking_number]).to_bytes() != shipment.tracking_commitment{
+            return err!(PhysicalMarketErrors::TrackingMismatch)
+        };
+        if (delivered_at < shipment.shipped_at) || (delivered_at > Clock::get()?.unix_timestamp){
+            return err!(PhysicalMarketErrors::InvalidDeliveryTime)
+        };
+        shipment.revealed = true;
+    };
+    ctx.accounts.physical_transaction.delivered_at = delivered_at;
+    ctx.accounts.physical_transaction.transition(TransactionState::BuyerConfirmedDelivery);
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// AUTO RELEASE (permissionless once the inspection window lapses)
+
+pub fn auto_release_sol<'a>(ctx: Context<'_, '_, '_, 'a, AutoReleaseSol<'a>>) -> Result<()>{
+    if !release_due(
+        Clock::get()?.unix_timestamp,
+        ctx.accounts.physical_transaction.shipped_at,
+        ctx.accounts.market_config.inspection_window,
+        ctx.accounts.physical_transaction.delivered_at,
+        ctx.accounts.market_config.dispute_window
+    ){
+        return err!(PhysicalMarketErrors::InspectionWindowOpen)
+    };
+    ctx.accounts.physical_transaction.apply_fee_tier(&ctx.accounts.seller_account, &ctx.accounts.fee_config);
+
+    let physical_tx = ctx.accounts.physical_transaction.key();
+    let physical_seed = physical_tx.as_ref();
+    let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_tx_log.as_ref();
+
+    if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
+        release_escrow_sol_helper(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.seller_wallet.to_account_info(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            ctx.accounts.multisig_wallet.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.buyer_account.used_reflink,
+            ctx.accounts.physical_transaction.metadata.rate,
+            ctx.accounts.physical_transaction.fees,
+            &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]]
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidEscrowBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::post_tx_incrementing!(
+            ctx.accounts.market_account_program.to_account_info(),
+            ctx.accounts.buyer_account.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+
+        increment_times_sold_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+pub fn auto_release_spl<'a>(ctx: Context<'_, '_, '_, 'a, AutoReleaseSpl<'a>>) -> Result<()>{
+    if !release_due(
+        Clock::get()?.unix_timestamp,
+        ctx.accounts.physical_transaction.shipped_at,
+        ctx.accounts.market_config.inspection_window,
+        ctx.accounts.physical_transaction.delivered_at,
+        ctx.accounts.market_config.dispute_window
+    ){
+        return err!(PhysicalMarketErrors::InspectionWindowOpen)
+    };
+    ctx.accounts.physical_transaction.apply_fee_tier(&ctx.accounts.seller_account, &ctx.accounts.fee_config);
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        release_escrow_spl_helper(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.seller_token_account.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.multisig_ata.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.buyer_account.used_reflink,
+            ctx.accounts.physical_transaction.metadata.rate,
+            ctx.accounts.physical_transaction.fees,
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::post_tx_incrementing!(
+            ctx.accounts.market_account_program.to_account_info(),
+            ctx.accounts.buyer_account.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+
+        increment_times_sold_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// BUYER CANCELLATION
+
+pub fn buyer_cancel_sol(ctx: Context<BuyerCancelSol>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        if ctx.accounts.physical_transaction.metadata.rate == 100{
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                        caller: ctx.accounts.physical_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }
+
+        update_product_quantity_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+pub fn buyer_cancel_spl(ctx: Context<BuyerCancelSpl>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        if ctx.accounts.physical_transaction.metadata.rate == 100{
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                        caller: ctx.accounts.physical_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }
+
+        update_product_quantity_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+        )?;
+
+        anchor_spl::token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                anchor_spl::token::CloseAccount{
+                    account: ctx.accounts.escrow_account.to_account_info(),
+                    destination: ctx.accounts.buyer_wallet.to_account_info(),
+                    authority: ctx.accounts.physical_auth.to_account_info()
+                },
+                &[&[b"market_authority", &[*auth_bump]]]
+            )
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// SHIP DEADLINE (buyer reclaims escrow if the seller never ships)
+
+pub fn reclaim_unshipped_sol(ctx: Context<ReclaimUnshippedSol>) -> Result<()>{
+    if Clock::get()?.unix_timestamp < ctx.accounts.physical_transaction.ship_by{
+        return err!(PhysicalMarketErrors::ShipDeadlineNotPassed)
+    };
+
+    let physical_tx = ctx.accounts.physical_transaction.key();
+    let physical_seed = physical_tx.as_ref();
+    let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_tx_log.as_ref();
+
+    if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
+        orbit_transaction::close_escrow_sol_rate!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
+            100
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidEscrowBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        if ctx.accounts.physical_transaction.metadata.rate == 100{
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                        caller: ctx.accounts.physical_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }
+
+        update_product_quantity_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+pub fn reclaim_unshipped_spl(ctx: Context<ReclaimUnshippedSpl>) -> Result<()>{
+    if Clock::get()?.unix_timestamp < ctx.accounts.physical_transaction.ship_by{
+        return err!(PhysicalMarketErrors::ShipDeadlineNotPassed)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::close_escrow_spl_rate!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.transaction_price,
+            100
+        )?;
+
+        if ctx.accounts.physical_transaction.metadata.rate == 100{
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                        caller: ctx.accounts.physical_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }
+
+        update_product_quantity_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// PARTIAL REFUNDS (one side proposes, the other accepts)
+/// only once the item shipped. before that a full cancel or decline restocks it instead
+
+#[derive(Accounts)]
+pub struct ProposePartialRefund<'info>{
+    #[account(
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery)
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        has_one = wallet,
+        constraint = {
+            (proposer_account.voter_id == physical_transaction.metadata.seller) ||
+            (proposer_account.voter_id == physical_transaction.metadata.buyer)
+        }
+    )]
+    pub proposer_account: Account<'info, OrbitMarketAccount>,
+
+    pub wallet: Signer<'info>,
+}
+
+/// checked against the same split acceptance runs, so a proposal that could never pay out is rejected here
+pub fn propose_partial_refund(ctx: Context<ProposePartialRefund>, buyer_amount: u64) -> Result<()>{
+    if partial_refund_split(
+        ctx.accounts.physical_transaction.escrowed_amount(),
+        buyer_amount,
+        ctx.accounts.physical_transaction.metadata.rate,
+        &ctx.accounts.physical_transaction.fees
+    ).is_none(){
+        return err!(PhysicalMarketErrors::InvalidRefundAmount)
+    };
+    ctx.accounts.physical_transaction.refund_proposal = Some(RefundProposal{
+        proposer: ctx.accounts.proposer_account.voter_id,
+        buyer_amount
+    });
+    Ok(())
+}
+
+/// whoever didnt propose the split has to be the one accepting it
+fn check_refund_acceptor(proposal: &RefundProposal, buyer: &OrbitMarketAccount, seller: &OrbitMarketAccount, acceptor: Pubkey) -> Result<()>{
+    let expected = if proposal.proposer == buyer.voter_id{
+        seller.wallet
+    }else{
+        buyer.wallet
+    };
+    if acceptor != expected{
+        return err!(PhysicalMarketErrors::InvalidTransactionInvoker)
+    };
+    Ok(())
+}
+
+pub fn accept_partial_refund_sol(ctx: Context<AcceptPartialRefundSol>, buyer_amount: u64) -> Result<()>{
+    let proposal = match ctx.accounts.physical_transaction.refund_proposal{
+        Some(p) => p,
+        None => return err!(PhysicalMarketErrors::NoRefundProposal)
+    };
+    // the proposer can rewrite the split at any time, so the acceptor names the one they agreed to
+    if proposal.buyer_amount != buyer_amount{
+        return err!(PhysicalMarketErrors::RefundProposalChanged)
+    };
+    check_refund_acceptor(&proposal, &ctx.accounts.buyer_account, &ctx.accounts.seller_account, ctx.accounts.wallet.key())?;
+    ctx.accounts.physical_transaction.apply_fee_tier(&ctx.accounts.seller_account, &ctx.accounts.fee_config);
+
+    let physical_tx = ctx.accounts.physical_transaction.key();
+    let physical_seed = physical_tx.as_ref();
+    let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_tx_log.as_ref();
+
+    if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
+        let split = match partial_refund_split(
+            ctx.accounts.escrow_account.lamports(),
+            proposal.buyer_amount,
+            ctx.accounts.physical_transaction.metadata.rate,
+            &ctx.accounts.physical_transaction.fees
+        ){
+            Some(split) => split,
+            None => return err!(PhysicalMarketErrors::InvalidRefundAmount)
+        };
+
+        orbit_transaction::close_escrow_sol_flat!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
+            split.buyer
+        ).expect("couldnt refund buyer");
+
+        if split.fee > 0{
+            orbit_transaction::close_escrow_sol_flat!(
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.multisig_wallet.to_account_info(),
+                &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
+                split.fee
+            ).expect("couldnt pay fees");
+        };
+
+        orbit_transaction::close_escrow_sol_rate!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.seller_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
+            100
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidEscrowBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::post_tx_incrementing!(
+            ctx.accounts.market_account_program.to_account_info(),
+            ctx.accounts.buyer_account.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+
+        increment_times_sold_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.refund_proposal = None;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
+    Ok(())
+}
+
+pub fn accept_partial_refund_spl(ctx: Context<AcceptPartialRefundSpl>, buyer_amount: u64) -> Result<()>{
+    let proposal = match ctx.accounts.physical_transaction.refund_proposal{
+        Some(p) => p,
+        None => return err!(PhysicalMarketErrors::NoRefundProposal)
+    };
+    // the proposer can rewrite the split at any time, so the acceptor names the one they agreed to
+    if proposal.buyer_amount != buyer_amount{
+        return err!(PhysicalMarketErrors::RefundProposalChanged)
+    };
+    check_refund_acceptor(&proposal, &ctx.accounts.buyer_account, &ctx.accounts.seller_account, ctx.accounts.wallet.key())?;
+    ctx.accounts.physical_transaction.apply_fee_tier(&ctx.accounts.seller_account, &ctx.accounts.fee_config);
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        let split = match partial_refund_split(
+            ctx.accounts.escrow_account.amount,
+            proposal.buyer_amount,
+            ctx.accounts.physical_transaction.metadata.rate,
+            &ctx.accounts.physical_transaction.fees
+        ){
+            Some(split) => split,
+            None => return err!(PhysicalMarketErrors::InvalidRefundAmount)
+        };
+
+        orbit_transaction::close_escrow_spl_flat!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            split.buyer
+        ).expect("couldnt refund buyer");
+
+        if split.fee > 0{
+            orbit_transaction::close_escrow_spl_flat!(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.escrow_account.to_account_info(),
+                ctx.accounts.multisig_ata.to_account_info(),
+                ctx.accounts.physical_auth.to_account_info(),
+                &[&[b"market_authority", &[*auth_bump]]],
+                split.fee
+            ).expect("couldnt pay fees");
+        };
+
+        orbit_transaction::close_escrow_spl_flat!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.seller_token_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            split.seller
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::post_tx_incrementing!(
+            ctx.accounts.market_account_program.to_account_info(),
+            ctx.accounts.buyer_account.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]]
+        )?;
+
+        increment_times_sold_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    ctx.accounts.physical_transaction.refund_proposal = None;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Sold);
+    ctx.accounts.physical_transaction.metadata.transaction_state = TransactionState::Closed;
     Ok(())
 }
 
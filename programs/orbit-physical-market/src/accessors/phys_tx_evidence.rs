@@ -0,0 +1,124 @@
+use anchor_lang::{
+    prelude::*,
+    AccountsClose
+};
+use market_accounts::OrbitMarketAccount;
+use orbit_transaction::TransactionState;
+use crate::{
+    PhysicalTransaction,
+    DisputeEvidence,
+    EvidenceEntry,
+    EvidenceRole,
+    errors::PhysicalMarketErrors
+};
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// DISPUTE EVIDENCE
+/// orbit_dispute doesnt expose who the jurors are, so any market account that isnt
+/// the buyer or seller gets tagged as a juror. each role has its own quota in the log
+
+#[derive(Accounts)]
+pub struct InitEvidenceLog<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 1400,
+        seeds = [
+            b"dispute_evidence",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub evidence_log: Box<Account<'info, DisputeEvidence>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_evidence",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub evidence_log: Box<Account<'info, DisputeEvidence>>,
+
+    pub submitter_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        address = submitter_account.wallet
+    )]
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseEvidenceLog<'info>{
+    #[account(
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Closed
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute_evidence",
+            physical_transaction.key().as_ref()
+        ],
+        bump,
+        has_one = payer
+    )]
+    pub evidence_log: Box<Account<'info, DisputeEvidence>>,
+
+    #[account(mut)]
+    pub payer: SystemAccount<'info>,
+}
+
+pub fn init_evidence_log(ctx: Context<InitEvidenceLog>) -> Result<()>{
+    ctx.accounts.evidence_log.physical_transaction = ctx.accounts.physical_transaction.key();
+    ctx.accounts.evidence_log.payer = ctx.accounts.payer.key();
+    ctx.accounts.evidence_log.entries = Vec::new();
+    Ok(())
+}
+
+pub fn submit_evidence(ctx: Context<SubmitEvidence>, content: [u8; 64]) -> Result<()>{
+    let submitter = ctx.accounts.submitter_account.voter_id;
+    let role = if submitter == ctx.accounts.physical_transaction.metadata.buyer{
+        EvidenceRole::Buyer
+    }else
+    if submitter == ctx.accounts.physical_transaction.metadata.seller{
+        EvidenceRole::Seller
+    }else{
+        EvidenceRole::Juror
+    };
+    if !ctx.accounts.evidence_log.has_room(submitter, role){
+        return err!(PhysicalMarketErrors::EvidenceLogFull)
+    };
+
+    ctx.accounts.evidence_log.entries.push(EvidenceEntry{
+        submitter,
+        role,
+        content,
+        submitted_at: Clock::get()?.unix_timestamp
+    });
+    Ok(())
+}
+
+pub fn close_evidence_log(ctx: Context<CloseEvidenceLog>) -> Result<()>{
+    ctx.accounts.evidence_log.close(ctx.accounts.payer.to_account_info())
+}
@@ -0,0 +1,304 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        system_instruction::transfer,
+        program::invoke_signed,
+    },
+};
+use anchor_spl::token::TokenAccount;
+use market_accounts::OrbitMarketAccount;
+use orbit_transaction::{
+    TransactionState,
+    BuyerOpenTransactions,
+};
+use crate::{
+    PhysicalTransaction,
+    PhysicalMarketConfig,
+    ShippingEnvelope,
+    ReturnState,
+    ReturnReason,
+    CartSettlement,
+    errors::PhysicalMarketErrors,
+    update_product_quantity_helper,
+    clear_transaction_logs_helper,
+    restore_coupon_use,
+
+    ConfirmReturnSol,
+    ConfirmReturnSpl,
+};
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// BUYER RETURN STEPS
+
+#[derive(Accounts)]
+pub struct BuyerReturnContext<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+}
+
+pub fn request_return(ctx: Context<BuyerReturnContext>, reason: ReturnReason) -> Result<()>{
+    if ctx.accounts.physical_transaction.return_state != ReturnState::None{
+        return err!(PhysicalMarketErrors::InvalidReturnState)
+    };
+    ctx.accounts.physical_transaction.return_reason = Some(reason);
+    ctx.accounts.physical_transaction.return_state = ReturnState::Requested;
+    ctx.accounts.physical_transaction.return_updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(enc_shipping: ShippingEnvelope)]
+pub struct BuyerReturnShipping<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery,
+        constraint = physical_transaction.return_state == ReturnState::Authorized,
+        realloc = PhysicalTransaction::space_with(&physical_transaction.shipping, &enc_shipping),
+        realloc::payer = buyer_wallet,
+        realloc::zero = false
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+pub fn update_return_shipping(ctx: Context<BuyerReturnShipping>, enc_shipping: ShippingEnvelope) -> Result<()>{
+    if !enc_shipping.is_valid(){
+        return err!(PhysicalMarketErrors::InvalidShippingEnvelope)
+    };
+    ctx.accounts.physical_transaction.return_shipping = enc_shipping;
+    ctx.accounts.physical_transaction.return_state = ReturnState::Shipped;
+    ctx.accounts.physical_transaction.return_updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/////////////////////////////////////////////////////////////////////////////////////////////
+/// SELLER RETURN STEPS
+
+#[derive(Accounts)]
+pub struct SellerReturnContext<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        constraint = seller_market_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        address = seller_market_account.wallet
+    )]
+    pub wallet: Signer<'info>,
+}
+
+pub fn authorize_return(ctx: Context<SellerReturnContext>) -> Result<()>{
+    if ctx.accounts.physical_transaction.return_state != ReturnState::Requested{
+        return err!(PhysicalMarketErrors::InvalidReturnState)
+    };
+    ctx.accounts.physical_transaction.return_state = ReturnState::Authorized;
+    ctx.accounts.physical_transaction.return_updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/// buyer can still dispute inside the dispute window, otherwise escrow releases as normal.
+/// an authorized return the buyer never ships can be rejected too, expiry may be disabled
+pub fn reject_return(ctx: Context<SellerReturnContext>) -> Result<()>{
+    if (ctx.accounts.physical_transaction.return_state != ReturnState::Requested) &&
+        (ctx.accounts.physical_transaction.return_state != ReturnState::Authorized)
+    {
+        return err!(PhysicalMarketErrors::InvalidReturnState)
+    };
+    ctx.accounts.physical_transaction.return_state = ReturnState::Rejected;
+    ctx.accounts.physical_transaction.return_updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExpireReturn<'info>{
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery,
+        constraint =
+        (physical_transaction.return_state == ReturnState::Requested) ||
+        (physical_transaction.return_state == ReturnState::Authorized)
+    )]
+    pub physical_transaction: Account<'info, PhysicalTransaction>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+}
+
+/// permissionless. a request the seller never answers, or a return the buyer never ships, stops blocking release
+pub fn expire_return(ctx: Context<ExpireReturn>) -> Result<()>{
+    let return_window = ctx.accounts.market_config.return_window;
+    if (return_window == 0) ||
+        (Clock::get()?.unix_timestamp <= ctx.accounts.physical_transaction.return_updated_at.saturating_add(return_window))
+    {
+        return err!(PhysicalMarketErrors::ReturnWindowOpen)
+    };
+    ctx.accounts.physical_transaction.return_state = ReturnState::Rejected;
+    Ok(())
+}
+
+pub fn confirm_return_sol(ctx: Context<ConfirmReturnSol>) -> Result<()>{
+    let physical_tx = ctx.accounts.physical_transaction.key();
+    let physical_seed = physical_tx.as_ref();
+    let buyer_tx_log = ctx.accounts.buyer_transactions_log.key();
+    let buyer_tx_log_seed = buyer_tx_log.as_ref();
+
+    if let Some(escrow_seeds) = ctx.bumps.get("escrow_account"){
+        orbit_transaction::close_escrow_sol_rate!(
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_wallet.to_account_info(),
+            &[&[b"orbit_escrow_account", physical_seed, buyer_tx_log_seed, &[*escrow_seeds]]],
+            100
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidEscrowBump)
+    };
+
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        if ctx.accounts.physical_transaction.metadata.rate == 100{
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                        caller: ctx.accounts.physical_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }
+
+        update_product_quantity_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+    ctx.accounts.physical_transaction.return_state = ReturnState::Received;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    ctx.accounts.physical_transaction.transition(TransactionState::Closed);
+    Ok(())
+}
+
+pub fn confirm_return_spl(ctx: Context<ConfirmReturnSpl>) -> Result<()>{
+    if let Some(auth_bump) = ctx.bumps.get("physical_auth"){
+        orbit_transaction::close_escrow_spl_rate!(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.escrow_account.to_account_info(),
+            ctx.accounts.buyer_token_account.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.transaction_price,
+            100
+        )?;
+
+        if ctx.accounts.physical_transaction.metadata.rate == 100{
+            market_accounts::cpi::increment_dispute_discounts(
+                CpiContext::new_with_signer(
+                    ctx.accounts.market_account_program.to_account_info(),
+                    market_accounts::cpi::accounts::MarketAccountUpdateInternal{
+                        market_account: ctx.accounts.buyer_account.to_account_info(),
+                        caller_auth: ctx.accounts.physical_auth.to_account_info(),
+                        caller: ctx.accounts.physical_program.to_account_info()
+                    },
+                    &[&[b"market_authority", &[*auth_bump]]]
+                )
+            )?;
+        }
+
+        update_product_quantity_helper(
+            ctx.accounts.product_program.to_account_info(),
+            ctx.accounts.phys_product.to_account_info(),
+            ctx.accounts.seller_account.to_account_info(),
+            ctx.accounts.seller_listings.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.phys_product.quantity + ctx.accounts.physical_transaction.quantity
+        )?;
+
+        clear_transaction_logs_helper(
+            ctx.accounts.transaction_program.to_account_info(),
+            ctx.accounts.seller_transactions_log.to_account_info(),
+            ctx.accounts.buyer_transactions_log.to_account_info(),
+            ctx.accounts.physical_auth.to_account_info(),
+            ctx.accounts.physical_program.to_account_info(),
+            &[&[b"market_authority", &[*auth_bump]]],
+            ctx.accounts.physical_transaction.metadata.seller_tx_index,
+            ctx.accounts.physical_transaction.metadata.buyer_tx_index
+        )?;
+    }else{
+        return err!(PhysicalMarketErrors::InvalidAuthBump)
+    };
+
+    restore_coupon_use(&ctx.accounts.coupon, ctx.accounts.physical_transaction.coupon)?;
+    ctx.accounts.physical_transaction.return_state = ReturnState::Received;
+    ctx.accounts.physical_transaction.queue_cart_settlement(CartSettlement::Restock);
+    ctx.accounts.physical_transaction.transition(TransactionState::Closed);
+    Ok(())
+}
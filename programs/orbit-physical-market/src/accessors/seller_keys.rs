@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use market_accounts::OrbitMarketAccount;
+use crate::SellerEncryptionKey;
+
+///////////////////////////////////////////////////////////////////
+/// SELLER ENCRYPTION KEYS (one per seller, rotated in place)
+
+#[derive(Accounts)]
+pub struct RegisterEncryptionKey<'info>{
+    #[account(
+        init,
+        payer = seller_wallet,
+        space = 100,
+        seeds = [
+            b"seller_encryption_key",
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub encryption_key: Account<'info, SellerEncryptionKey>,
+
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct RotateEncryptionKey<'info>{
+    #[account(
+        mut,
+        seeds = [
+            b"seller_encryption_key",
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub encryption_key: Account<'info, SellerEncryptionKey>,
+
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+}
+
+pub fn register_encryption_key(ctx: Context<RegisterEncryptionKey>, key: [u8; 32]) -> Result<()>{
+    ctx.accounts.encryption_key.seller = ctx.accounts.seller_account.voter_id;
+    ctx.accounts.encryption_key.key = key;
+    ctx.accounts.encryption_key.version = 1;
+    ctx.accounts.encryption_key.updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+pub fn rotate_encryption_key(ctx: Context<RotateEncryptionKey>, key: [u8; 32]) -> Result<()>{
+    ctx.accounts.encryption_key.key = key;
+    ctx.accounts.encryption_key.version += 1;
+    ctx.accounts.encryption_key.updated_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+/// key version an order is opened under. 0 when the seller hasnt registered a key
+pub fn encryption_key_version(encryption_key: &AccountInfo) -> u32{
+    match Account::<SellerEncryptionKey>::try_from(encryption_key){
+        Ok(key) => key.version,
+        Err(_) => 0
+    }
+}
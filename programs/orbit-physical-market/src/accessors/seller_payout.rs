@@ -0,0 +1,77 @@
+use anchor_lang::{
+    prelude::*,
+    AccountsClose
+};
+use market_accounts::OrbitMarketAccount;
+use crate::SellerPayout;
+
+///////////////////////////////////////////////////////////////////
+/// SELLER PAYOUT DESTINATIONS (one per seller per currency)
+
+#[derive(Accounts)]
+#[instruction(currency: Pubkey)]
+pub struct RegisterSellerPayout<'info>{
+    #[account(
+        init,
+        payer = seller_wallet,
+        space = 100,
+        seeds = [
+            b"seller_payout",
+            &seller_account.voter_id.to_le_bytes(),
+            currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: Account<'info, SellerPayout>,
+
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    pub system_program: Program<'info, System>
+}
+
+#[derive(Accounts)]
+pub struct UpdateSellerPayout<'info>{
+    #[account(
+        mut,
+        constraint = seller_payout.seller == seller_account.voter_id
+    )]
+    pub seller_payout: Account<'info, SellerPayout>,
+
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+}
+
+pub fn register_seller_payout(ctx: Context<RegisterSellerPayout>, currency: Pubkey, destination: Pubkey) -> Result<()>{
+    ctx.accounts.seller_payout.seller = ctx.accounts.seller_account.voter_id;
+    ctx.accounts.seller_payout.currency = currency;
+    ctx.accounts.seller_payout.destination = destination;
+    Ok(())
+}
+
+pub fn update_seller_payout(ctx: Context<UpdateSellerPayout>, destination: Pubkey) -> Result<()>{
+    ctx.accounts.seller_payout.destination = destination;
+    Ok(())
+}
+
+pub fn remove_seller_payout(ctx: Context<UpdateSellerPayout>) -> Result<()>{
+    ctx.accounts.seller_payout.close(ctx.accounts.seller_wallet.to_account_info())
+}
+
+/// where seller proceeds go. falls back to the market account wallet when nothing is registered
+pub fn payout_destination(seller_payout: &AccountInfo, wallet: Pubkey) -> Pubkey{
+    match Account::<SellerPayout>::try_from(seller_payout){
+        Ok(payout) => payout.destination,
+        Err(_) => wallet
+    }
+}
@@ -13,6 +13,11 @@ use orbit_transaction::{transaction_struct::TransactionState, SellerOpenTransact
 use orbit_product::PhysicalProduct;
 use crate::{
     PhysicalTransaction,
+    ReturnState,
+    PhysicalMarketConfig,
+    PhysicalFeeConfig,
+    SellerCoupon,
+    payout_destination,
     program::OrbitPhysicalMarket
 };
 
@@ -25,7 +30,7 @@ pub struct OpenPhysicalTransactionSol<'info>{
     #[account(
         init,
         payer = buyer_wallet,
-        space = 400,
+        space = PhysicalTransaction::BASE_SPACE,
         seeds = [
             b"orbit_physical_transaction",
             seller_transactions_log.key().as_ref(),
@@ -56,7 +61,7 @@ pub struct OpenPhysicalTransactionSol<'info>{
         mut,
         seeds = [
             b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Digital).try_to_vec()?).as_slice(),
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
             &buyer_market_account.voter_id.to_le_bytes()
         ], 
         bump,
@@ -82,7 +87,7 @@ pub struct OpenPhysicalTransactionSol<'info>{
         mut,
         seeds = [
             b"vendor_listings",
-            (&(orbit_product::ListingsType::Commissions).try_to_vec()?).as_slice(),
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
             &seller_market_account.voter_id.to_le_bytes()
         ],
         bump,
@@ -94,7 +99,7 @@ pub struct OpenPhysicalTransactionSol<'info>{
         mut,
         seeds = [
             b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
             &seller_market_account.voter_id.to_le_bytes()
         ], 
         bump,
@@ -108,6 +113,25 @@ pub struct OpenPhysicalTransactionSol<'info>{
         bump
     )]
     pub physical_auth: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
+    #[account(mut)]
+    pub coupon: Option<Account<'info, SellerCoupon>>,
+
+    /// CHECK: only read through encryption_key_version, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_encryption_key",
+            &seller_market_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub seller_encryption_key: UncheckedAccount<'info>,
     
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
 
@@ -162,7 +186,7 @@ pub struct ClosePhysicalTransactionSol<'info>{
         mut,
         seeds = [
             b"buyer_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
             &buyer_account.voter_id.to_le_bytes()
         ], 
         bump,
@@ -187,7 +211,7 @@ pub struct ClosePhysicalTransactionSol<'info>{
         mut,
         seeds = [
             b"seller_transactions",
-            (&(orbit_transaction::TransactionType::Commissions).try_to_vec()?).as_slice(),
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
             &seller_account.voter_id.to_le_bytes()
         ], 
         bump,
@@ -195,9 +219,20 @@ pub struct ClosePhysicalTransactionSol<'info>{
     )]
     pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
 
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
     #[account(
         mut,
-        address = seller_account.wallet
+        address = payout_destination(&seller_payout, seller_account.wallet)
     )]
     pub seller_wallet: SystemAccount<'info>,
 
@@ -216,6 +251,12 @@ pub struct ClosePhysicalTransactionSol<'info>{
     )]
     pub physical_auth: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
     
     pub market_account_program: Program<'info, OrbitMarketAccounts>,
@@ -226,15 +267,25 @@ pub struct ClosePhysicalTransactionSol<'info>{
 }
 
 #[derive(Accounts)]
-pub struct FundEscrowSol<'info>{
+pub struct AcceptPartialRefundSol<'info>{
     ////////////////////////////////////////////
     /// TX
     #[account(
         mut,
-        constraint = physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery),
+        constraint = physical_transaction.refund_proposal.is_some()
     )]
     pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
     
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
     #[account(
         mut,
         seeds = [
@@ -246,16 +297,22 @@ pub struct FundEscrowSol<'info>{
     )]
     pub escrow_account: SystemAccount<'info>,
 
-    ////////////////////////////////////////////
-    /// BUYER SELLER
-
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
     /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
     #[account(
         mut,
         seeds = [
             b"buyer_transactions",
             (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
-            &buyer_market_account.voter_id.to_le_bytes()
+            &buyer_account.voter_id.to_le_bytes()
         ], 
         bump,
         seeds::program = &orbit_transaction::id()
@@ -264,26 +321,102 @@ pub struct FundEscrowSol<'info>{
 
     #[account(
         mut,
-        constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
+        address = buyer_account.wallet
     )]
-    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+    pub buyer_wallet: SystemAccount<'info>,
     
+    /// SELLER
+    #[account(
+        mut,
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
 
     #[account(
         mut,
-        address = buyer_market_account.wallet
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = payout_destination(&seller_payout, seller_account.wallet)
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    #[account(
+        constraint = (wallet.key() == buyer_account.wallet) || (wallet.key() == seller_account.wallet)
+    )]
+    pub wallet: Signer<'info>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+    
+    #[account(
+        mut,
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_wallet: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
     )]
-    pub buyer_wallet: Signer<'info>
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>
 }
 
 #[derive(Accounts)]
-pub struct SellerEarlyDeclineSol<'info>{
+pub struct AutoReleaseSol<'info>{
     ////////////////////////////////////////////
     /// TX
     #[account(
-        mut
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery),
+        constraint =
+        (physical_transaction.return_state == ReturnState::None) ||
+        (physical_transaction.return_state == ReturnState::Rejected)
     )]
     pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+    
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
 
     #[account(
         mut,
@@ -306,7 +439,6 @@ pub struct SellerEarlyDeclineSol<'info>{
     )]
     pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
     
-    /// BUYER
     #[account(
         mut,
         seeds = [
@@ -318,7 +450,6 @@ pub struct SellerEarlyDeclineSol<'info>{
         seeds::program = &orbit_transaction::id()
     )]
     pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
-    
 
     #[account(
         mut,
@@ -327,13 +458,12 @@ pub struct SellerEarlyDeclineSol<'info>{
     pub buyer_wallet: SystemAccount<'info>,
     
     /// SELLER
-
     #[account(
         mut,
         constraint = seller_account.voter_id == physical_transaction.metadata.seller
     )]
     pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
-    
+
     #[account(
         mut,
         seeds = [
@@ -346,14 +476,31 @@ pub struct SellerEarlyDeclineSol<'info>{
     )]
     pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
 
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
     #[account(
         mut,
-        address = seller_account.wallet
+        address = payout_destination(&seller_payout, seller_account.wallet)
     )]
-    pub seller_wallet: Signer<'info>,
+    pub seller_wallet: SystemAccount<'info>,
 
     //////////////////////////////////
     /// CPI AND EXTRANEOUS
+    
+    #[account(
+        mut,
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_wallet: SystemAccount<'info>,
 
     #[account(
         seeds = [b"market_authority"],
@@ -361,23 +508,34 @@ pub struct SellerEarlyDeclineSol<'info>{
     )]
     pub physical_auth: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
     
     pub market_account_program: Program<'info, OrbitMarketAccounts>,
 
+    pub product_program: Program<'info, OrbitProduct>,
+
     pub transaction_program: Program<'info, OrbitTransaction>
 }
 
-////////////////////////////////////////////////////
-/// DISPUTE UTILS
-
 #[derive(Accounts)]
-pub struct ClosePhysicalDisputeSol<'info>{
+pub struct FundEscrowSol<'info>{
     ////////////////////////////////////////////
     /// TX
     #[account(
         mut,
-        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed
     )]
     pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
     
@@ -391,38 +549,76 @@ pub struct ClosePhysicalDisputeSol<'info>{
         bump
     )]
     pub escrow_account: SystemAccount<'info>,
-    
+
     ////////////////////////////////////////////
-    /// DISPUTE
+    /// BUYER SELLER
+
+    /// BUYER
     #[account(
         mut,
-        constraint = phys_dispute.dispute_transaction == physical_transaction.key(),
-        constraint = phys_dispute.dispute_state == DisputeState::Resolved,
-        has_one = funder
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
     )]
-    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
 
     #[account(
         mut,
-        seeds = [
-            b"orbit_account",
-            favor_wallet.key().as_ref()
-        ],
-        bump,
-        seeds::program = market_accounts::ID,
-        
-        constraint = favor_market_account.voter_id == phys_dispute.favor
+        constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
     )]
-    pub favor_market_account: Box<Account<'info, OrbitMarketAccount>>,
-        
+    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+
     #[account(
         mut,
-        address = favor_market_account.wallet
+        address = buyer_market_account.wallet
     )]
-    pub favor_wallet: SystemAccount<'info>,
+    pub buyer_wallet: Signer<'info>,
 
-    #[account(mut)]
-    pub funder: SystemAccount<'info>,
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>
+}
+
+#[derive(Accounts)]
+pub struct SellerEarlyDeclineSol<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Opened) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded)
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: SystemAccount<'info>,
 
     ///////////////////////////////////////////////////
     /// BUYER SELLER ACCOUNTS
@@ -434,6 +630,7 @@ pub struct ClosePhysicalDisputeSol<'info>{
     )]
     pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
     
+    /// BUYER
     #[account(
         mut,
         seeds = [
@@ -446,18 +643,32 @@ pub struct ClosePhysicalDisputeSol<'info>{
     )]
     pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
     
+
     #[account(
         mut,
         address = buyer_account.wallet
     )]
     pub buyer_wallet: SystemAccount<'info>,
-
+    
     /// SELLER
+
     #[account(
         mut,
         constraint = seller_account.voter_id == physical_transaction.metadata.seller
     )]
     pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
     
     #[account(
         mut,
@@ -471,25 +682,536 @@ pub struct ClosePhysicalDisputeSol<'info>{
     )]
     pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
 
-    //////////////////////////////////
-    /// CPI AND EXTRANEOUS
-    
     #[account(
         mut,
-        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+        address = seller_account.wallet
     )]
-    pub multisig_wallet: SystemAccount<'info>,
-    
+    pub seller_wallet: Signer<'info>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
     #[account(
         seeds = [b"market_authority"],
         bump
     )]
     pub physical_auth: SystemAccount<'info>,
-    
+
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
     
-    pub market_accounts_program: Program<'info, OrbitMarketAccounts>,
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>
+}
+
+#[derive(Accounts)]
+pub struct BuyerCancelSol<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Opened) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed)
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimUnshippedSol<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: SystemAccount<'info>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmReturnSol<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery,
+        constraint = physical_transaction.return_state == ReturnState::Shipped
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: SystemAccount<'info>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
     
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+}
+
+////////////////////////////////////////////////////
+/// DISPUTE UTILS
+
+#[derive(Accounts)]
+pub struct ClosePhysicalDisputeSol<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: SystemAccount<'info>,
+    
+    ////////////////////////////////////////////
+    /// DISPUTE
+    #[account(
+        mut,
+        constraint = phys_dispute.dispute_transaction == physical_transaction.key(),
+        has_one = funder
+    )]
+    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+
+    #[account(
+        mut,
+        constraint = (favor_market_account.voter_id == phys_dispute.favor) || (phys_dispute.dispute_state != DisputeState::Resolved)
+    )]
+    pub favor_market_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
+    /// CHECK: only read through resolution_buyer_bps, may not be initialized
+    #[account(
+        seeds = [
+            b"dispute_resolution",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub dispute_resolution: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = payout_destination(&seller_payout, seller_account.wallet)
+    )]
+    pub seller_wallet: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+    
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    /// SELLER
+    #[account(
+        mut,
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+    
+    #[account(
+        mut,
+        address = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_wallet: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+    
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_accounts_program: Program<'info, OrbitMarketAccounts>,
+    
+    pub product_program: Program<'info, OrbitProduct>,
+
     pub transaction_program: Program<'info, OrbitTransaction>,
 
     pub dispute_program: Program<'info, Dispute>,
@@ -18,6 +18,11 @@ use orbit_transaction::{transaction_struct::TransactionState, SellerOpenTransact
 use orbit_product::PhysicalProduct;
 use crate::{
     PhysicalTransaction,
+    ReturnState,
+    PhysicalMarketConfig,
+    PhysicalFeeConfig,
+    SellerCoupon,
+    payout_destination,
     program::OrbitPhysicalMarket
 };
 
@@ -32,7 +37,7 @@ pub struct OpenPhysicalTransactionSpl<'info>{
     #[account(
         init,
         payer = buyer_wallet,
-        space = 1000,
+        space = PhysicalTransaction::BASE_SPACE,
         seeds = [
             b"orbit_physical_transaction",
             seller_transactions_log.key().as_ref(),
@@ -100,7 +105,7 @@ pub struct OpenPhysicalTransactionSpl<'info>{
         mut,
         seeds = [
             b"vendor_listings",
-            (&(orbit_product::ListingsType::Commissions).try_to_vec()?).as_slice(),
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
             &seller_market_account.voter_id.to_le_bytes()
         ],
         bump,
@@ -128,6 +133,25 @@ pub struct OpenPhysicalTransactionSpl<'info>{
     )]
     pub physical_auth: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+
+    #[account(mut)]
+    pub coupon: Option<Account<'info, SellerCoupon>>,
+
+    /// CHECK: only read through encryption_key_version, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_encryption_key",
+            &seller_market_account.voter_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub seller_encryption_key: UncheckedAccount<'info>,
+
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
 
     pub market_account_program: Program<'info, OrbitMarketAccounts>,
@@ -218,9 +242,20 @@ pub struct ClosePhysicalTransactionSpl<'info>{
     )]
     pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
 
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
     #[account(
         mut,
-        token::authority = seller_account.wallet
+        constraint = seller_token_account.owner == payout_destination(&seller_payout, seller_account.wallet)
     )]
     pub seller_token_account: Account<'info, TokenAccount>,
 
@@ -233,8 +268,15 @@ pub struct ClosePhysicalTransactionSpl<'info>{
         bump
     )]
     pub physical_auth: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
     
     #[account(
+        mut,
         token::authority = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
     )]
     pub multisig_ata: Account<'info, TokenAccount>,
@@ -252,13 +294,24 @@ pub struct ClosePhysicalTransactionSpl<'info>{
 }
 
 #[derive(Accounts)]
-pub struct SellerEarlyDeclineSpl<'info>{
+pub struct AcceptPartialRefundSpl<'info>{
     //////////////////////////////////
     /// TX
     #[account(
-        mut
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery),
+        constraint = physical_transaction.refund_proposal.is_some()
     )]
     pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
     
     #[account(
         mut,
@@ -276,16 +329,17 @@ pub struct SellerEarlyDeclineSpl<'info>{
     
     /// BUYER
     #[account(
-        constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
     )]
-    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
 
     #[account(
         mut,
         seeds = [
             b"buyer_transactions",
             (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
-            &buyer_market_account.voter_id.to_le_bytes()
+            &buyer_account.voter_id.to_le_bytes()
         ], 
         bump,
         seeds::program = &orbit_transaction::id()
@@ -294,18 +348,17 @@ pub struct SellerEarlyDeclineSpl<'info>{
 
     #[account(
         mut,
-        token::authority = buyer_market_account.wallet
+        token::authority = buyer_account.wallet
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
 
     /// SELLER
-
     #[account(
         mut,
         constraint = seller_account.voter_id == physical_transaction.metadata.seller
     )]
     pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
-    
+
     #[account(
         mut,
         seeds = [
@@ -318,12 +371,29 @@ pub struct SellerEarlyDeclineSpl<'info>{
     )]
     pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
 
+    /// CHECK: only read through payout_destination, may not be initialized
     #[account(
-        address = seller_account.wallet
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
     )]
-    pub seller_wallet: Signer<'info>,
+    pub seller_payout: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == payout_destination(&seller_payout, seller_account.wallet)
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
 
     
+    #[account(
+        constraint = (wallet.key() == buyer_account.wallet) || (wallet.key() == seller_account.wallet)
+    )]
+    pub wallet: Signer<'info>,
+
     //////////////////////////////////
     /// CPI AND EXTRANEOUS
     
@@ -333,9 +403,23 @@ pub struct SellerEarlyDeclineSpl<'info>{
     )]
     pub physical_auth: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
+    
+    #[account(
+        mut,
+        token::authority = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_ata: Account<'info, TokenAccount>,
+
     pub market_account_program: Program<'info, OrbitMarketAccounts>,
     
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub product_program: Program<'info, OrbitProduct>,
 
     pub transaction_program: Program<'info, OrbitTransaction>,
 
@@ -344,14 +428,26 @@ pub struct SellerEarlyDeclineSpl<'info>{
 }
 
 #[derive(Accounts)]
-pub struct FundEscrowSpl<'info>{
-    ////////////////////////////////////////////
+pub struct AutoReleaseSpl<'info>{
+    //////////////////////////////////
     /// TX
     #[account(
         mut,
-        constraint = physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Shipped) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery),
+        constraint =
+        (physical_transaction.return_state == ReturnState::None) ||
+        (physical_transaction.return_state == ReturnState::Rejected)
     )]
     pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
     
     #[account(
         mut,
@@ -364,21 +460,22 @@ pub struct FundEscrowSpl<'info>{
     )]
     pub escrow_account: Account<'info, TokenAccount>,
 
-    ////////////////////////////////////////////
+    //////////////////////////////////
     /// BUYER SELLER
     
     /// BUYER
     #[account(
-        constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
     )]
-    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
 
     #[account(
         mut,
         seeds = [
             b"buyer_transactions",
             (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
-            &buyer_market_account.voter_id.to_le_bytes()
+            &buyer_account.voter_id.to_le_bytes()
         ], 
         bump,
         seeds::program = &orbit_transaction::id()
@@ -387,35 +484,105 @@ pub struct FundEscrowSpl<'info>{
 
     #[account(
         mut,
-        token::authority = buyer_wallet.key()
+        token::authority = buyer_account.wallet
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
 
+    /// SELLER
     #[account(
-        address = buyer_market_account.wallet
+        mut,
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
     )]
-    pub buyer_wallet: Signer<'info>,
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == payout_destination(&seller_payout, seller_account.wallet)
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
 
+    
     //////////////////////////////////
     /// CPI AND EXTRANEOUS
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
 
-    pub token_program: Program<'info, Token>
-}
+    #[account(
+        seeds = [b"physical_fee_config"],
+        bump
+    )]
+    pub fee_config: Account<'info, PhysicalFeeConfig>,
 
-////////////////////////////////////////////////////
-/// DISPUTE UTILS
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+    
+    #[account(
+        mut,
+        token::authority = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_ata: Account<'info, TokenAccount>,
 
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+    
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub token_program: Program<'info, Token>,
+    
+}
 
 #[derive(Accounts)]
-pub struct ClosePhysicalDisputeSpl<'info>{
-    ////////////////////////////////////////////
+pub struct SellerEarlyDeclineSpl<'info>{
+    //////////////////////////////////
     /// TX
-    
     #[account(
         mut,
-        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Opened) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded)
     )]
     pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
     
     #[account(
         mut,
@@ -427,42 +594,15 @@ pub struct ClosePhysicalDisputeSpl<'info>{
         bump
     )]
     pub escrow_account: Account<'info, TokenAccount>,
-    
-    /////////////////////////////////////////////////////
-    /// DISPUTE RELATED
-    
-    #[account(
-        mut,
-        constraint = phys_dispute.dispute_transaction == physical_transaction.key(),
-        constraint = phys_dispute.dispute_state == DisputeState::Resolved,
-        has_one = funder
-    )]
-    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
-    
-    #[account(
-        mut,
-        token::authority = favor_market_account.wallet
-    )]
-    pub favor_token_account: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        constraint = favor_market_account.voter_id == phys_dispute.favor
-    )]
-    pub favor_market_account: Box<Account<'info, OrbitMarketAccount>>,
-    
-    #[account(mut)]
-    pub funder: SystemAccount<'info>,
 
-    
-    ///////////////////////////////////////////////////
-    /// BUYER SELLER ACCOUNTS
+    //////////////////////////////////
+    /// BUYER SELLER
     
     /// BUYER
     #[account(
         constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
     )]
-    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
 
     #[account(
         mut,
@@ -481,13 +621,26 @@ pub struct ClosePhysicalDisputeSpl<'info>{
         token::authority = buyer_market_account.wallet
     )]
     pub buyer_token_account: Account<'info, TokenAccount>,
-    
+
     /// SELLER
+
     #[account(
         mut,
         constraint = seller_account.voter_id == physical_transaction.metadata.seller
     )]
     pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
     
     #[account(
         mut,
@@ -501,6 +654,19 @@ pub struct ClosePhysicalDisputeSpl<'info>{
     )]
     pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
 
+    #[account(
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
     //////////////////////////////////
     /// CPI AND EXTRANEOUS
     
@@ -510,15 +676,615 @@ pub struct ClosePhysicalDisputeSpl<'info>{
     )]
     pub physical_auth: SystemAccount<'info>,
 
-    #[account(
-        token::authority = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
-    )]
-    pub multisig_ata: Account<'info, TokenAccount>,
-
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+    
     pub physical_program: Program<'info, OrbitPhysicalMarket>,
 
-    pub dispute_program: Program<'info, Dispute>,
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub token_program: Program<'info, Token>,
+    
+}
+
+#[derive(Accounts)]
+pub struct FundEscrowSpl<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed,
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
     
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+
+    ////////////////////////////////////////////
+    /// BUYER SELLER
+    
+    /// BUYER
+    #[account(
+        constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_market_account: Account<'info, OrbitMarketAccount>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        token::authority = buyer_wallet.key()
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        address = buyer_market_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    pub token_program: Program<'info, Token>
+}
+
+#[derive(Accounts)]
+pub struct BuyerCancelSpl<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint =
+        (physical_transaction.metadata.transaction_state == TransactionState::Opened) ||
+        (physical_transaction.metadata.transaction_state == TransactionState::SellerConfirmed)
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimUnshippedSpl<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerFunded
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        token::authority = buyer_account.wallet
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ConfirmReturnSpl<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::BuyerConfirmedDelivery,
+        constraint = physical_transaction.return_state == ReturnState::Shipped
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        address = buyer_account.wallet
+    )]
+    pub buyer_wallet: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = buyer_account.wallet
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    /// SELLER
+    #[account(
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        address = seller_account.wallet
+    )]
+    pub seller_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+    
+    pub market_account_program: Program<'info, OrbitMarketAccounts>,
+
+    pub product_program: Program<'info, OrbitProduct>,
+
+    pub transaction_program: Program<'info, OrbitTransaction>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+////////////////////////////////////////////////////
+/// DISPUTE UTILS
+
+
+#[derive(Accounts)]
+pub struct ClosePhysicalDisputeSpl<'info>{
+    ////////////////////////////////////////////
+    /// TX
+    
+    #[account(
+        mut,
+        constraint = physical_transaction.metadata.transaction_state == TransactionState::Frozen
+    )]
+    pub physical_transaction: Box<Account<'info, PhysicalTransaction>>,
+
+    #[account(
+        mut,
+        constraint = phys_product.metadata.index == physical_transaction.metadata.product,
+        constraint = phys_product.metadata.owner_catalog == seller_account.voter_id
+    )] 
+    pub phys_product: Box<Account<'info, PhysicalProduct>>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"orbit_escrow_account",
+            physical_transaction.key().as_ref(),
+            buyer_transactions_log.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: Account<'info, TokenAccount>,
+    
+    /////////////////////////////////////////////////////
+    /// DISPUTE RELATED
+    
+    #[account(
+        mut,
+        constraint = phys_dispute.dispute_transaction == physical_transaction.key(),
+        has_one = funder
+    )]
+    pub phys_dispute: Box<Account<'info, OrbitDispute>>,
+    
+    /// CHECK: only read through payout_destination, may not be initialized
+    #[account(
+        seeds = [
+            b"seller_payout",
+            &physical_transaction.metadata.seller.to_le_bytes(),
+            physical_transaction.metadata.currency.as_ref()
+        ],
+        bump
+    )]
+    pub seller_payout: UncheckedAccount<'info>,
+
+    /// CHECK: only read through resolution_buyer_bps, may not be initialized
+    #[account(
+        seeds = [
+            b"dispute_resolution",
+            physical_transaction.key().as_ref()
+        ],
+        bump
+    )]
+    pub dispute_resolution: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == payout_destination(&seller_payout, seller_account.wallet)
+    )]
+    pub seller_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = (favor_market_account.voter_id == phys_dispute.favor) || (phys_dispute.dispute_state != DisputeState::Resolved)
+    )]
+    pub favor_market_account: Box<Account<'info, OrbitMarketAccount>>,
+    
+    #[account(mut)]
+    pub funder: SystemAccount<'info>,
+
+    
+    ///////////////////////////////////////////////////
+    /// BUYER SELLER ACCOUNTS
+    
+    /// BUYER
+    #[account(
+        mut,
+        constraint = buyer_market_account.voter_id == physical_transaction.metadata.buyer
+    )]
+    pub buyer_market_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"buyer_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &buyer_market_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub buyer_transactions_log: Box<Account<'info, BuyerOpenTransactions>>,
+
+    #[account(
+        mut,
+        token::authority = buyer_market_account.wallet
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+    
+    /// SELLER
+    #[account(
+        mut,
+        constraint = seller_account.voter_id == physical_transaction.metadata.seller
+    )]
+    pub seller_account: Box<Account<'info, OrbitMarketAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vendor_listings",
+            (&(orbit_product::ListingsType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ],
+        bump,
+        seeds::program = &orbit_product::id()
+    )]
+    pub seller_listings: Account<'info, ListingsStruct>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"seller_transactions",
+            (&(orbit_transaction::TransactionType::Physical).try_to_vec()?).as_slice(),
+            &seller_account.voter_id.to_le_bytes()
+        ], 
+        bump,
+        seeds::program = &orbit_transaction::id()
+    )]
+    pub seller_transactions_log: Box<Account<'info, SellerOpenTransactions>>,
+
+    /// CHECK: the coupon redeemed at open, only read through restore_coupon_use. may be closed by now
+    #[account(
+        mut,
+        address = physical_transaction.coupon.unwrap_or_default()
+    )]
+    pub coupon: Option<UncheckedAccount<'info>>,
+
+    //////////////////////////////////
+    /// CPI AND EXTRANEOUS
+    
+    #[account(
+        seeds = [b"market_authority"],
+        bump
+    )]
+    pub physical_auth: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"physical_market_config"],
+        bump
+    )]
+    pub market_config: Account<'info, PhysicalMarketConfig>,
+
+    #[account(
+        mut,
+        token::authority = Pubkey::from(orbit_addresses::MULTISIG_SIGNER)
+    )]
+    pub multisig_ata: Account<'info, TokenAccount>,
+
+    pub physical_program: Program<'info, OrbitPhysicalMarket>,
+
+    pub dispute_program: Program<'info, Dispute>,
+    
+    pub product_program: Program<'info, OrbitProduct>,
+
     pub transaction_program: Program<'info, OrbitTransaction>,
 
     pub token_program: Program<'info, Token>,
@@ -16,4 +16,60 @@ pub enum PhysicalMarketErrors{
     InvalidTransactionInvoker,
     #[msg("Please confirm delivery first")]
     DidNotConfirmDelivery,
+    #[msg("product has not been shipped yet")]
+    NotShipped,
+    #[msg("inspection or post delivery dispute window has not passed yet")]
+    InspectionWindowOpen,
+    #[msg("seller still has time to ship")]
+    ShipDeadlineNotPassed,
+    #[msg("handling time has to be positive and within the market maximum")]
+    InvalidHandlingTime,
+    #[msg("refund cant exceed the transaction price")]
+    InvalidRefundAmount,
+    #[msg("no refund has been proposed")]
+    NoRefundProposal,
+    #[msg("refund proposal changed since it was reviewed")]
+    RefundProposalChanged,
+    #[msg("return can not be expired yet")]
+    ReturnWindowOpen,
+    #[msg("return is not in the right state for this")]
+    InvalidReturnState,
+    #[msg("not enough stock for that quantity")]
+    InvalidQuantity,
+    #[msg("order total overflows")]
+    PriceOverflow,
+    #[msg("price or currency does not match the product listing")]
+    ListingPriceMismatch,
+    #[msg("cart products dont match the quantities or the stored cart")]
+    InvalidCartItems,
+    #[msg("fee schedule bps values are out of range")]
+    InvalidFeeSchedule,
+    #[msg("coupon is expired, used up or doesnt apply to this order")]
+    InvalidCoupon,
+    #[msg("the coupon redeemed at open has to be passed")]
+    MissingCoupon,
+    #[msg("order used a reflink but its account wasnt passed")]
+    MissingReflinkAccount,
+    #[msg("no room left in the evidence log for this submitter")]
+    EvidenceLogFull,
+    #[msg("shipping envelope has an unknown scheme or bad ciphertext length")]
+    InvalidShippingEnvelope,
+    #[msg("shipping address is already sealed, the gift recipient cant change")]
+    ShippingAlreadySealed,
+    #[msg("shipment record is missing a carrier name")]
+    InvalidShipment,
+    #[msg("tracking number does not match the shipment commitment")]
+    TrackingMismatch,
+    #[msg("delivery time is before shipment or in the future")]
+    InvalidDeliveryTime,
+    #[msg("resolution split has to be between 0 and 10000 bps")]
+    InvalidResolution,
+    #[msg("too late to open a dispute for this delivery")]
+    DisputeWindowClosed,
+    #[msg("dispute isnt resolved and default judgment isnt due")]
+    DisputeNotResolved,
+    #[msg("appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("ruling still open to appeal")]
+    AppealWindowOpen,
 }
\ No newline at end of file
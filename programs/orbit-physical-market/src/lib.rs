@@ -21,8 +21,12 @@ pub mod orbit_physical_market {
     /// TRANSACTION HANDLERS
 
     /// SOL
-    pub fn open_transaction_sol(ctx: Context<OpenPhysicalTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
-        PhysicalTransaction::open_sol(ctx, seller_index, buyer_index, price, use_discount)
+    pub fn open_transaction_sol(ctx: Context<OpenPhysicalTransactionSol>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, quantity: u32) -> Result<()>{
+        phys_tx_common::open_sol_quantity(ctx, seller_index, buyer_index, price, use_discount, quantity)
+    }
+
+    pub fn open_cart_transaction_sol<'a>(ctx: Context<'_, '_, '_, 'a, OpenPhysicalTransactionSol<'a>>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, quantities: Vec<u32>) -> Result<()>{
+        phys_tx_cart::open_cart_sol(ctx, seller_index, buyer_index, price, use_discount, quantities)
     }
 
     pub fn close_transaction_sol<'a>(ctx: Context<'_, '_, '_, 'a, ClosePhysicalTransactionSol<'a>>) -> Result<()>{
@@ -37,9 +41,33 @@ pub mod orbit_physical_market {
         PhysicalTransaction::seller_early_decline_sol(ctx)
     }
 
+    pub fn buyer_cancel_sol(ctx: Context<BuyerCancelSol>) -> Result<()>{
+        phys_tx_common::buyer_cancel_sol(ctx)
+    }
+
+    pub fn auto_release_sol<'a>(ctx: Context<'_, '_, '_, 'a, AutoReleaseSol<'a>>) -> Result<()>{
+        phys_tx_common::auto_release_sol(ctx)
+    }
+
+    pub fn reclaim_unshipped_sol(ctx: Context<ReclaimUnshippedSol>) -> Result<()>{
+        phys_tx_common::reclaim_unshipped_sol(ctx)
+    }
+
+    pub fn accept_partial_refund_sol(ctx: Context<AcceptPartialRefundSol>, buyer_amount: u64) -> Result<()>{
+        phys_tx_common::accept_partial_refund_sol(ctx, buyer_amount)
+    }
+
+    pub fn confirm_return_sol(ctx: Context<ConfirmReturnSol>) -> Result<()>{
+        phys_tx_returns::confirm_return_sol(ctx)
+    }
+
     /// SPL
-    pub fn open_transaction_spl(ctx: Context<OpenPhysicalTransactionSpl>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool) -> Result<()>{
-        PhysicalTransaction::open_spl(ctx, seller_index, buyer_index, price, use_discount)
+    pub fn open_transaction_spl(ctx: Context<OpenPhysicalTransactionSpl>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, quantity: u32) -> Result<()>{
+        phys_tx_common::open_spl_quantity(ctx, seller_index, buyer_index, price, use_discount, quantity)
+    }
+
+    pub fn open_cart_transaction_spl<'a>(ctx: Context<'_, '_, '_, 'a, OpenPhysicalTransactionSpl<'a>>, seller_index: u8, buyer_index: u8, price: u64, use_discount: bool, quantities: Vec<u32>) -> Result<()>{
+        phys_tx_cart::open_cart_spl(ctx, seller_index, buyer_index, price, use_discount, quantities)
     }
 
     pub fn close_transaction_spl<'a>(ctx: Context<'_, '_, '_, 'a, ClosePhysicalTransactionSpl<'a>>) -> Result<()>{
@@ -54,14 +82,50 @@ pub mod orbit_physical_market {
         PhysicalTransaction::seller_early_decline_spl(ctx)
     }
 
+    pub fn buyer_cancel_spl(ctx: Context<BuyerCancelSpl>) -> Result<()>{
+        phys_tx_common::buyer_cancel_spl(ctx)
+    }
+
+    pub fn auto_release_spl<'a>(ctx: Context<'_, '_, '_, 'a, AutoReleaseSpl<'a>>) -> Result<()>{
+        phys_tx_common::auto_release_spl(ctx)
+    }
+
+    pub fn reclaim_unshipped_spl(ctx: Context<ReclaimUnshippedSpl>) -> Result<()>{
+        phys_tx_common::reclaim_unshipped_spl(ctx)
+    }
+
+    pub fn accept_partial_refund_spl(ctx: Context<AcceptPartialRefundSpl>, buyer_amount: u64) -> Result<()>{
+        phys_tx_common::accept_partial_refund_spl(ctx, buyer_amount)
+    }
+
+    pub fn confirm_return_spl(ctx: Context<ConfirmReturnSpl>) -> Result<()>{
+        phys_tx_returns::confirm_return_spl(ctx)
+    }
+
     /// COMMON
     pub fn close_transaction_account(ctx: Context<CloseTransactionAccount>) -> Result<()>{
         PhysicalTransaction::close_transaction_account(ctx)
     }
 
+    pub fn settle_cart_items<'a>(ctx: Context<'_, '_, '_, 'a, SettleCartItems<'a>>) -> Result<()>{
+        phys_tx_cart::settle_cart_items(ctx)
+    }
+
+    pub fn propose_partial_refund(ctx: Context<ProposePartialRefund>, buyer_amount: u64) -> Result<()>{
+        phys_tx_common::propose_partial_refund(ctx, buyer_amount)
+    }
+
     ////////////////////////////////////
     /// DISPUTE RELATED
 
+    pub fn post_dispute_bond_sol(ctx: Context<PostDisputeBondSol>) -> Result<()>{
+        dispute_bonds::post_dispute_bond_sol(ctx)
+    }
+
+    pub fn post_dispute_bond_spl(ctx: Context<PostDisputeBondSpl>) -> Result<()>{
+        dispute_bonds::post_dispute_bond_spl(ctx)
+    }
+
     pub fn open_dispute(ctx: Context<OpenPhysicalDispute>, threshold: u8) -> Result<()>{
         PhysicalTransaction::open_dispute(ctx, threshold)
     }
@@ -74,6 +138,228 @@ pub mod orbit_physical_market {
         PhysicalTransaction::close_dispute_spl(ctx)
     }
 
+    pub fn default_judgment_sol(ctx: Context<ClosePhysicalDisputeSol>) -> Result<()>{
+        phys_tx_common::settle_dispute_sol(ctx, true)
+    }
+
+    pub fn default_judgment_spl(ctx: Context<ClosePhysicalDisputeSpl>) -> Result<()>{
+        phys_tx_common::settle_dispute_spl(ctx, true)
+    }
+
+    pub fn close_defaulted_dispute(ctx: Context<CloseDefaultedDispute>) -> Result<()>{
+        phys_tx_common::close_defaulted_dispute(ctx)
+    }
+
+    pub fn record_dispute_ruling(ctx: Context<RecordDisputeRuling>) -> Result<()>{
+        dispute_appeals::record_dispute_ruling(ctx)
+    }
+
+    pub fn appeal_dispute_sol(ctx: Context<AppealDisputeSol>) -> Result<()>{
+        dispute_appeals::appeal_dispute_sol(ctx)
+    }
+
+    pub fn appeal_dispute_spl(ctx: Context<AppealDisputeSpl>) -> Result<()>{
+        dispute_appeals::appeal_dispute_spl(ctx)
+    }
+
+    pub fn settle_appeal_bond_sol(ctx: Context<SettleAppealBondSol>) -> Result<()>{
+        dispute_appeals::settle_appeal_bond_sol(ctx)
+    }
+
+    pub fn settle_appeal_bond_spl(ctx: Context<SettleAppealBondSpl>) -> Result<()>{
+        dispute_appeals::settle_appeal_bond_spl(ctx)
+    }
+
+    pub fn settle_dispute_bond_sol(ctx: Context<SettleDisputeBondSol>) -> Result<()>{
+        dispute_bonds::settle_dispute_bond_sol(ctx)
+    }
+
+    pub fn settle_dispute_bond_spl(ctx: Context<SettleDisputeBondSpl>) -> Result<()>{
+        dispute_bonds::settle_dispute_bond_spl(ctx)
+    }
+
+    pub fn reclaim_dispute_bond_sol(ctx: Context<ReclaimDisputeBondSol>) -> Result<()>{
+        dispute_bonds::reclaim_dispute_bond_sol(ctx)
+    }
+
+    pub fn reclaim_dispute_bond_spl(ctx: Context<ReclaimDisputeBondSpl>) -> Result<()>{
+        dispute_bonds::reclaim_dispute_bond_spl(ctx)
+    }
+
+    pub fn set_dispute_resolution(ctx: Context<SetDisputeResolution>, buyer_bps: u16) -> Result<()>{
+        dispute_resolution::set_dispute_resolution(ctx, buyer_bps)
+    }
+
+    pub fn close_dispute_resolution(ctx: Context<CloseDisputeResolution>) -> Result<()>{
+        dispute_resolution::close_dispute_resolution(ctx)
+    }
+
+    pub fn init_evidence_log(ctx: Context<InitEvidenceLog>) -> Result<()>{
+        phys_tx_evidence::init_evidence_log(ctx)
+    }
+
+    pub fn submit_evidence(ctx: Context<SubmitEvidence>, content: [u8; 64]) -> Result<()>{
+        phys_tx_evidence::submit_evidence(ctx, content)
+    }
+
+    pub fn close_evidence_log(ctx: Context<CloseEvidenceLog>) -> Result<()>{
+        phys_tx_evidence::close_evidence_log(ctx)
+    }
+
+    /////////////////////////////////////////////////
+    /// SHIPPING AND DELIVERY
+
+    pub fn seller_accept_transaction(ctx: Context<SellerAcceptTransaction>, seller_note: Option<[u8; 64]>, estimated_ship_date: Option<i64>, handling_time: Option<i64>) -> Result<()>{
+        phys_tx_common::seller_accept_transaction(ctx, seller_note, estimated_ship_date, handling_time)
+    }
+
+    pub fn update_shipping(ctx: Context<SellerConfirmationsContext>, carrier: Carrier, tracking_commitment: [u8; 32]) -> Result<()>{
+        phys_tx_common::update_shipping(ctx, carrier, tracking_commitment)
+    }
+
+    pub fn reveal_tracking(ctx: Context<RevealTracking>, tracking_number: Vec<u8>, salt: [u8; 32]) -> Result<()>{
+        phys_tx_common::reveal_tracking(ctx, tracking_number, salt)
+    }
+
+    pub fn confirm_delivery(ctx: Context<BuyerConfirm>) -> Result<()>{
+        phys_tx_common::confirm_delivery(ctx)
+    }
+
+    pub fn confirm_product(ctx: Context<BuyerConfirm>) -> Result<()>{
+        phys_tx_common::confirm_product(ctx)
+    }
+
+    pub fn set_gift_recipient(ctx: Context<SetGiftRecipient>, recipient: Option<Pubkey>) -> Result<()>{
+        phys_tx_common::set_gift_recipient(ctx, recipient)
+    }
+
+    pub fn recipient_confirm_delivery(ctx: Context<RecipientConfirm>) -> Result<()>{
+        phys_tx_common::recipient_confirm_delivery(ctx)
+    }
+
+    pub fn set_shipping_address(ctx: Context<SetShippingAddress>, enc_shipping: ShippingEnvelope) -> Result<()>{
+        phys_tx_common::set_shipping_address(ctx, enc_shipping)
+    }
+
+    pub fn attest_delivery(ctx: Context<AttestDelivery>, tracking_number: Vec<u8>, salt: [u8; 32], delivered_at: i64) -> Result<()>{
+        phys_tx_common::attest_delivery(ctx, tracking_number, salt, delivered_at)
+    }
+
+    /////////////////////////////////////////////////
+    /// RETURNS
+
+    pub fn request_return(ctx: Context<BuyerReturnContext>, reason: ReturnReason) -> Result<()>{
+        phys_tx_returns::request_return(ctx, reason)
+    }
+
+    pub fn authorize_return(ctx: Context<SellerReturnContext>) -> Result<()>{
+        phys_tx_returns::authorize_return(ctx)
+    }
+
+    pub fn update_return_shipping(ctx: Context<BuyerReturnShipping>, enc_shipping: ShippingEnvelope) -> Result<()>{
+        phys_tx_returns::update_return_shipping(ctx, enc_shipping)
+    }
+
+    pub fn reject_return(ctx: Context<SellerReturnContext>) -> Result<()>{
+        phys_tx_returns::reject_return(ctx)
+    }
+
+    pub fn expire_return(ctx: Context<ExpireReturn>) -> Result<()>{
+        phys_tx_returns::expire_return(ctx)
+    }
+
+    /////////////////////////////////////////////////
+    /// MARKET CONFIG
+
+    pub fn init_market_config(ctx: Context<InitMarketConfig>, inspection_window: i64, default_handling_time: i64) -> Result<()>{
+        market_config::init_market_config(ctx, inspection_window, default_handling_time)
+    }
+
+    pub fn set_inspection_window(ctx: Context<UpdateMarketConfig>, inspection_window: i64) -> Result<()>{
+        market_config::set_inspection_window(ctx, inspection_window)
+    }
+
+    pub fn set_default_handling_time(ctx: Context<UpdateMarketConfig>, default_handling_time: i64) -> Result<()>{
+        market_config::set_default_handling_time(ctx, default_handling_time)
+    }
+
+    pub fn set_max_handling_time(ctx: Context<UpdateMarketConfig>, max_handling_time: i64) -> Result<()>{
+        market_config::set_max_handling_time(ctx, max_handling_time)
+    }
+
+    pub fn set_return_window(ctx: Context<UpdateMarketConfig>, return_window: i64) -> Result<()>{
+        market_config::set_return_window(ctx, return_window)
+    }
+
+    pub fn set_dispute_bond(ctx: Context<UpdateMarketConfig>, dispute_bond_bps: u16, dispute_bond_floor: u64) -> Result<()>{
+        market_config::set_dispute_bond(ctx, dispute_bond_bps, dispute_bond_floor)
+    }
+
+    pub fn set_dispute_windows(ctx: Context<UpdateMarketConfig>, dispute_window: i64, resolution_window: i64, default_buyer_bps: u16) -> Result<()>{
+        market_config::set_dispute_windows(ctx, dispute_window, resolution_window, default_buyer_bps)
+    }
+
+    pub fn set_appeals(ctx: Context<UpdateMarketConfig>, appeal_window: i64, appeal_threshold: u8) -> Result<()>{
+        market_config::set_appeals(ctx, appeal_window, appeal_threshold)
+    }
+
+    pub fn init_fee_config(ctx: Context<InitFeeConfig>, fees: FeeSchedule) -> Result<()>{
+        market_config::init_fee_config(ctx, fees)
+    }
+
+    pub fn set_fee_schedule(ctx: Context<UpdateFeeConfig>, fees: FeeSchedule) -> Result<()>{
+        market_config::set_fee_schedule(ctx, fees)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<UpdateFeeConfig>, tiers: Vec<FeeTier>) -> Result<()>{
+        market_config::set_fee_tiers(ctx, tiers)
+    }
+
+    pub fn init_delivery_oracle(ctx: Context<InitDeliveryOracle>, signer: Pubkey) -> Result<()>{
+        market_config::init_delivery_oracle(ctx, signer)
+    }
+
+    pub fn set_delivery_oracle(ctx: Context<UpdateDeliveryOracle>, signer: Pubkey) -> Result<()>{
+        market_config::set_delivery_oracle(ctx, signer)
+    }
+
+    /////////////////////////////////////////////////
+    /// COUPONS
+
+    pub fn create_coupon(ctx: Context<CreateCoupon>, code: [u8; 16], discount: CouponDiscount, max_uses: u32, expires_at: Option<i64>, product: Option<u64>) -> Result<()>{
+        coupons::create_coupon(ctx, code, discount, max_uses, expires_at, product)
+    }
+
+    pub fn close_coupon(ctx: Context<CloseCoupon>) -> Result<()>{
+        coupons::close_coupon(ctx)
+    }
+
+    /////////////////////////////////////////////////
+    /// SELLER PAYOUTS
+
+    pub fn register_seller_payout(ctx: Context<RegisterSellerPayout>, currency: Pubkey, destination: Pubkey) -> Result<()>{
+        seller_payout::register_seller_payout(ctx, currency, destination)
+    }
+
+    pub fn update_seller_payout(ctx: Context<UpdateSellerPayout>, destination: Pubkey) -> Result<()>{
+        seller_payout::update_seller_payout(ctx, destination)
+    }
+
+    pub fn remove_seller_payout(ctx: Context<UpdateSellerPayout>) -> Result<()>{
+        seller_payout::remove_seller_payout(ctx)
+    }
+
+    /////////////////////////////////////////////////
+    /// SELLER ENCRYPTION KEYS
+
+    pub fn register_encryption_key(ctx: Context<RegisterEncryptionKey>, key: [u8; 32]) -> Result<()>{
+        seller_keys::register_encryption_key(ctx, key)
+    }
+
+    pub fn rotate_encryption_key(ctx: Context<RotateEncryptionKey>, key: [u8; 32]) -> Result<()>{
+        seller_keys::rotate_encryption_key(ctx, key)
+    }
+
     /////////////////////////////////////////////////
     /// REVIEW RELATED
     
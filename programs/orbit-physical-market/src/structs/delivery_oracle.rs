@@ -0,0 +1,6 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct DeliveryOracle{
+    pub signer: Pubkey, // carrier polling service allowed to attest deliveries
+}
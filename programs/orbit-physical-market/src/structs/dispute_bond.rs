@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct DisputeBond{
+    pub physical_transaction: Pubkey,
+    pub opener: u64, // voter_id of whoever posted the bond
+    pub opener_wallet: Pubkey,
+    pub amount: u64, // in the transactions currency
+    pub used: bool, // set once a dispute or appeal was opened with it
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct DisputeEvidence{
+    pub physical_transaction: Pubkey,
+    pub payer: Pubkey, // gets the rent back once the tx closes
+    pub entries: Vec<EvidenceEntry>,
+}
+
+impl DisputeEvidence{
+    pub const MAX_ENTRIES: usize = 16;
+    pub const MAX_PARTY_ENTRIES: usize = 6; // buyer and seller each, so outsiders cant crowd them out
+    pub const MAX_JUROR_ENTRIES: usize = 4; // shared by everyone tagged juror, one entry each
+
+    pub fn has_room(&self, submitter: u64, role: EvidenceRole) -> bool{
+        let by_role = self.entries.iter().filter(|entry| entry.role == role).count();
+        match role{
+            EvidenceRole::Juror =>
+                (by_role < Self::MAX_JUROR_ENTRIES) &&
+                !self.entries.iter().any(|entry| entry.submitter == submitter),
+            _ => by_role < Self::MAX_PARTY_ENTRIES
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EvidenceEntry{
+    pub submitter: u64, // voter_id
+    pub role: EvidenceRole,
+    pub content: [u8; 64], // ipfs cid or sha256 digest, zero padded
+    pub submitted_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum EvidenceRole{
+    Buyer,
+    Seller,
+    Juror
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct DisputeResolution{
+    pub physical_transaction: Pubkey,
+    pub payer: Pubkey, // gets the rent back once the tx closes
+    pub buyer_bps: u16, // share of escrow refunded to the buyer, the seller gets the rest
+}
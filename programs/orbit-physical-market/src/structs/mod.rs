@@ -1,2 +1,29 @@
 pub mod physical_transaction;
-pub use physical_transaction::*;
\ No newline at end of file
+pub use physical_transaction::*;
+
+pub mod physical_market_config;
+pub use physical_market_config::*;
+
+pub mod physical_fee_config;
+pub use physical_fee_config::*;
+
+pub mod seller_payout;
+pub use seller_payout::*;
+
+pub mod seller_coupon;
+pub use seller_coupon::*;
+
+pub mod dispute_evidence;
+pub use dispute_evidence::*;
+
+pub mod dispute_resolution;
+pub use dispute_resolution::*;
+
+pub mod dispute_bond;
+pub use dispute_bond::*;
+
+pub mod seller_encryption_key;
+pub use seller_encryption_key::*;
+
+pub mod delivery_oracle;
+pub use delivery_oracle::*;
\ No newline at end of file
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct PhysicalFeeConfig{
+    pub fees: FeeSchedule,
+    pub tiers: Vec<FeeTier>, // reputation discounts on the platform fee
+}
+
+impl PhysicalFeeConfig{
+    pub const MAX_FEE_TIERS: usize = 8;
+
+    /// cheapest tier the seller qualifies for. rating is the average review * 100
+    pub fn seller_tier(&self, transactions: u64, rating: u64) -> Option<FeeTier>{
+        self.tiers.iter()
+            .filter(|tier| (transactions >= tier.min_transactions) && (rating >= tier.min_rating as u64))
+            .min_by_key(|tier| tier.platform_fee_bps)
+            .copied()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeSchedule{
+    pub platform_fee_bps: u16, // taken from the seller payout at close
+    pub reflink_share_bps: u16, // out of the platform fee, paid to both the reflink owner and the buyer
+    pub dispute_discount_bps: u16, // knocked off the price when a buyer spends a dispute discount
+}
+
+impl FeeSchedule{
+    pub fn platform_fee(&self, amount: u64) -> u64{
+        bps_of(amount, self.platform_fee_bps)
+    }
+
+    pub fn reflink_share(&self, amount: u64) -> u64{
+        bps_of(amount, self.reflink_share_bps)
+    }
+
+    pub fn discounted_price(&self, price: u64) -> u64{
+        bps_of(price, 10000u16.saturating_sub(self.dispute_discount_bps))
+    }
+}
+
+/// widened so large spl amounts dont overflow. bps are capped at 10000, so it fits back in a u64
+fn bps_of(amount: u64, bps: u16) -> u64{
+    ((amount as u128) * (bps.min(10000) as u128) / 10000) as u64
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeTier{
+    pub min_transactions: u64,
+    pub min_rating: u16, // average review * 100, so 450 is 4.5 stars
+    pub platform_fee_bps: u16,
+}
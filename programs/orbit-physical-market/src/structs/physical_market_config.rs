@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct PhysicalMarketConfig{
+    pub inspection_window: i64, // seconds after shipment before escrow can auto release
+    pub default_handling_time: i64, // seconds after funding the seller has to ship if they didnt declare one
+    pub dispute_bond_bps: u16, // bond a dispute opener posts, as bps of the transaction price
+    pub dispute_bond_floor: u64, // minimum bond in the smallest unit of the tx currency
+    pub dispute_window: i64, // seconds after delivery the buyer can still open a dispute
+    pub resolution_window: i64, // seconds a frozen tx waits on jurors before default judgment
+    pub default_buyer_bps: u16, // escrow split applied by default judgment
+    pub appeal_window: i64, // seconds after a recorded ruling the loser can appeal
+    pub appeal_threshold: u8, // juror threshold for the appeal dispute
+    pub max_handling_time: i64, // longest handling time a seller can declare. 0 means sellers cant declare one
+    pub return_window: i64, // seconds a requested or authorized return can sit before anyone can expire it. 0 disables expiry
+}
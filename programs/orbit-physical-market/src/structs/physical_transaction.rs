@@ -1,8 +1,164 @@
 use anchor_lang::prelude::*;
-use orbit_transaction::transaction_struct::OrbitTransactionStruct;
+use orbit_transaction::{
+    transaction_struct::OrbitTransactionStruct,
+    TransactionState
+};
+use market_accounts::OrbitMarketAccount;
+use crate::{
+    FeeSchedule,
+    FeeTier,
+    PhysicalFeeConfig
+};
 
 #[account]
 pub struct PhysicalTransaction{
     pub metadata: OrbitTransactionStruct, // 32 * 3 + 5?
-    pub shipping: [u8; 64],
-}
\ No newline at end of file
+    pub shipping: ShippingEnvelope,
+    pub seller_key_version: u32, // seller encryption key version shipping is sealed to, 0 if none was registered
+    pub quantity: u32,
+    pub seller_note: Option<[u8; 64]>,
+    pub estimated_ship_date: Option<i64>,
+    pub handling_time: Option<i64>,
+    pub ship_by: i64,
+    pub shipped_at: i64,
+    pub shipment: Option<ShipmentRecord>, // set by the seller on update_shipping
+    pub refund_proposal: Option<RefundProposal>,
+    pub return_state: ReturnState,
+    pub return_reason: Option<ReturnReason>,
+    pub return_shipping: ShippingEnvelope, // return tracking the buyer seals to the seller
+    pub return_updated_at: i64, // last return step, a stalled request or authorization expires from here
+    pub cart: Vec<CartItem>, // products beyond metadata.product for cart checkouts
+    pub cart_settlement: CartSettlement,
+    pub fees: FeeSchedule, // fee terms in effect when the tx was opened
+    pub fee_tier: Option<FeeTier>, // reputation tier applied at close, if any
+    pub gift_recipient: Option<Pubkey>, // shipping is encrypted for this key and it can confirm delivery
+    pub coupon: Option<Pubkey>, // seller coupon redeemed at open
+    pub coupon_discount: u64, // taken off the listed price by the coupon
+    pub dispute_buyer_bps: Option<u16>, // how escrow was split when a dispute closed
+    pub dispute_defaulted: bool, // closed by default judgment, the orbit_dispute is still open
+    pub delivered_at: i64,
+    pub frozen_at: i64,
+    pub ruling_at: i64, // when the current dispute ruling was recorded, starts the appeal window
+    pub appealed: bool,
+}
+
+impl PhysicalTransaction{
+    pub const MAX_CART_ITEMS: usize = 8;
+    pub const MAX_QUANTITY: u32 = 16; // units per tx across every line. times sold is bumped one cpi per unit at close
+    pub const BASE_SPACE: usize = 1000; // allocated at open with an empty shipping envelope
+
+    /// space the account needs to hold both sealed envelopes
+    pub fn space_with(shipping: &ShippingEnvelope, return_shipping: &ShippingEnvelope) -> usize{
+        Self::BASE_SPACE + shipping.ciphertext.len() + return_shipping.ciphertext.len()
+    }
+
+    /// what funding moved into escrow. transaction_price is stored after the coupon and, at rate 100,
+    /// the dispute discount came off the listed price
+    pub fn escrowed_amount(&self) -> u64{
+        self.metadata.transaction_price
+    }
+
+    /// a pending refund proposal was made against the old state, so it lapses on every move
+    pub fn transition(&mut self, state: TransactionState){
+        self.refund_proposal = None;
+        self.metadata.transaction_state = state;
+    }
+
+    pub fn queue_cart_settlement(&mut self, settlement: CartSettlement){
+        if !self.cart.is_empty(){
+            self.cart_settlement = settlement;
+        }
+    }
+
+    /// lowers the platform fee to the sellers reputation tier. never raises it above the terms from open
+    pub fn apply_fee_tier(&mut self, seller: &OrbitMarketAccount, config: &PhysicalFeeConfig){
+        let reviews: u64 = seller.reputation.iter().sum();
+        let rating = if reviews == 0{
+            0
+        }else{
+            seller.reputation.iter().enumerate().map(|(stars, count)| (stars as u64 + 1) * count).sum::<u64>() * 100 / reviews
+        };
+
+        if let Some(tier) = config.seller_tier(seller.transactions, rating){
+            if tier.platform_fee_bps < self.fees.platform_fee_bps{
+                self.fees.platform_fee_bps = tier.platform_fee_bps;
+                self.fees.reflink_share_bps = self.fees.reflink_share_bps.min(tier.platform_fee_bps / 2);
+                self.fee_tier = Some(tier);
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RefundProposal{
+    pub proposer: u64, // voter_id of whoever proposed
+    pub buyer_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ReturnState{
+    None,
+    Requested,
+    Authorized,
+    Shipped,
+    Received,
+    Rejected // seller refused or the request expired. escrow can release again
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ReturnReason{
+    WrongItem,
+    Defective,
+    NotAsDescribed,
+    Other
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CartItem{
+    pub product: u64,
+    pub quantity: u32,
+    pub unit_price: u64, // listed price when the cart was opened
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum CartSettlement{
+    None,
+    Restock,
+    Sold
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ShippingEnvelope{
+    pub scheme: u8, // 0 until the envelope is written
+    pub ephemeral_key: [u8; 32], // x25519 key the sender generated for this envelope
+    pub nonce: [u8; 24],
+    pub ciphertext: Vec<u8>, // sealed address, auth tag included
+}
+
+impl ShippingEnvelope{
+    pub const SCHEME_X25519_XCHACHA20POLY1305: u8 = 1;
+    pub const MAX_CIPHERTEXT_LEN: usize = 512;
+
+    pub fn is_valid(&self) -> bool{
+        (self.scheme == Self::SCHEME_X25519_XCHACHA20POLY1305) &&
+        !self.ciphertext.is_empty() &&
+        (self.ciphertext.len() <= Self::MAX_CIPHERTEXT_LEN)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum Carrier{
+    Usps,
+    Ups,
+    Fedex,
+    Dhl,
+    Other([u8; 16]) // carrier name, zero padded
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ShipmentRecord{
+    pub carrier: Carrier,
+    pub tracking_commitment: [u8; 32], // sha256(salt || tracking number)
+    pub shipped_at: i64,
+    pub revealed: bool, // seller opened the commitment during a dispute
+}
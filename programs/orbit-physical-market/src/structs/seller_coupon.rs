@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct SellerCoupon{
+    pub seller: u64, // voter_id of the issuing seller
+    pub code: [u8; 16],
+    pub discount: CouponDiscount,
+    pub max_uses: u32,
+    pub uses: u32,
+    pub expires_at: Option<i64>,
+    pub product: Option<u64>, // only valid when this product is the one being bought
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum CouponDiscount{
+    Percent{
+        bps: u16
+    },
+    Flat{
+        amount: u64,
+        currency: Pubkey // flat amounts only make sense in one currency
+    }
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct SellerEncryptionKey{
+    pub seller: u64, // voter_id of the seller
+    pub key: [u8; 32], // x25519 public key buyers seal shipping envelopes to
+    pub version: u32, // bumped on every rotation. orders record the version they were opened under
+    pub updated_at: i64,
+}
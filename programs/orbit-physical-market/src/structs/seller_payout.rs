@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct SellerPayout{
+    pub seller: u64, // voter_id of the seller
+    pub currency: Pubkey, // System::id() for sol, the mint for spl
+    pub destination: Pubkey, // wallet (sol) or token account owner (spl) that receives proceeds
+}
@@ -0,0 +1,197 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { OrbitPhysicalMarket } from "../target/types/orbit_physical_market";
+
+// the market, product, transaction and dispute programs have to be deployed to the local validator
+// with their idls in target/idl, the same way the orbit programs are laid out next to each other.
+// createUser, listProduct and resolveDispute call those programs directly and follow their interfaces
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.OrbitPhysicalMarket as Program<OrbitPhysicalMarket>;
+export const accountsProgram = anchor.workspace.OrbitMarketAccounts as Program<any>;
+export const productProgram = anchor.workspace.OrbitProduct as Program<any>;
+export const transactionProgram = anchor.workspace.OrbitTransaction as Program<any>;
+export const disputeProgram = anchor.workspace.Dispute as Program<any>;
+
+// the provider wallet is the multisig signer on localnet
+export const multisig = provider.wallet.publicKey;
+
+/////////////////////////////////
+/// PDAS
+
+const voterSeed = (voterId: BN) => voterId.toArrayLike(Buffer, "le", 8);
+const physicalTxType = () => transactionProgram.coder.types.encode("TransactionType", { physical: {} });
+const physicalListingsType = () => productProgram.coder.types.encode("ListingsType", { physical: {} });
+
+const pda = (seeds: (Buffer | Uint8Array)[], programId: PublicKey = program.programId) =>
+  PublicKey.findProgramAddressSync(seeds, programId)[0];
+
+export const marketConfig = () => pda([Buffer.from("physical_market_config")]);
+export const feeConfig = () => pda([Buffer.from("physical_fee_config")]);
+export const physicalAuth = () => pda([Buffer.from("market_authority")]);
+
+export const buyerLog = (voterId: BN) =>
+  pda([Buffer.from("buyer_transactions"), physicalTxType(), voterSeed(voterId)], transactionProgram.programId);
+export const sellerLog = (voterId: BN) =>
+  pda([Buffer.from("seller_transactions"), physicalTxType(), voterSeed(voterId)], transactionProgram.programId);
+export const listings = (voterId: BN) =>
+  pda([Buffer.from("vendor_listings"), physicalListingsType(), voterSeed(voterId)], productProgram.programId);
+
+export const physicalTransaction = (sellerTxLog: PublicKey, sellerIndex: number) =>
+  pda([Buffer.from("orbit_physical_transaction"), sellerTxLog.toBuffer(), Buffer.from([sellerIndex])]);
+export const escrow = (tx: PublicKey, buyerTxLog: PublicKey) =>
+  pda([Buffer.from("orbit_escrow_account"), tx.toBuffer(), buyerTxLog.toBuffer()]);
+export const sellerPayout = (voterId: BN, currency: PublicKey) =>
+  pda([Buffer.from("seller_payout"), voterSeed(voterId), currency.toBuffer()]);
+export const sellerEncryptionKey = (voterId: BN) =>
+  pda([Buffer.from("seller_encryption_key"), voterSeed(voterId)]);
+export const disputeBond = (tx: PublicKey, voterId: BN) =>
+  pda([Buffer.from("dispute_bond"), tx.toBuffer(), voterSeed(voterId)]);
+export const appealBond = (tx: PublicKey) => pda([Buffer.from("appeal_bond"), tx.toBuffer()]);
+export const disputeResolution = (tx: PublicKey) => pda([Buffer.from("dispute_resolution"), tx.toBuffer()]);
+export const disputeAccount = (tx: PublicKey) =>
+  pda([Buffer.from("dispute_account"), tx.toBuffer()], disputeProgram.programId);
+
+/////////////////////////////////
+/// USERS AND LISTINGS
+
+export type MarketUser = {
+  wallet: Keypair,
+  account: PublicKey,
+  voterId: BN,
+  buyerLog: PublicKey,
+  sellerLog: PublicKey,
+};
+
+export async function airdrop(to: PublicKey, sol = 10) {
+  const sig = await provider.connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  await provider.connection.confirmTransaction(sig, "confirmed");
+}
+
+export async function createUser(): Promise<MarketUser> {
+  const wallet = Keypair.generate();
+  await airdrop(wallet.publicKey);
+
+  const account = pda([Buffer.from("orbit_account"), wallet.publicKey.toBuffer()], accountsProgram.programId);
+  await accountsProgram.methods
+    .createAccount("", "")
+    .accounts({
+      marketAccount: account,
+      wallet: wallet.publicKey,
+      payer: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([wallet])
+    .rpc();
+  const voterId: BN = (await accountsProgram.account.orbitMarketAccount.fetch(account)).voterId;
+
+  const user = {
+    wallet,
+    account,
+    voterId,
+    buyerLog: buyerLog(voterId),
+    sellerLog: sellerLog(voterId),
+  };
+
+  await transactionProgram.methods
+    .createBuyerPhysicalTransactionsLog()
+    .accounts({
+      transactionsLog: user.buyerLog,
+      marketAccount: account,
+      wallet: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([wallet])
+    .rpc();
+  await transactionProgram.methods
+    .createSellerPhysicalTransactionsLog()
+    .accounts({
+      transactionsLog: user.sellerLog,
+      marketAccount: account,
+      wallet: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([wallet])
+    .rpc();
+  await productProgram.methods
+    .initPhysicalListings()
+    .accounts({
+      vendorListings: listings(voterId),
+      vendorAccount: account,
+      wallet: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([wallet])
+    .rpc();
+
+  return user;
+}
+
+/// lists a sol priced product and returns its address
+export async function listProduct(seller: MarketUser, price: BN, quantity: number): Promise<PublicKey> {
+  const product = Keypair.generate();
+  await productProgram.methods
+    .listPhysicalProduct({
+      index: 0,
+      price,
+      deliveryEstimate: 0,
+      media: "",
+      currency: SystemProgram.programId,
+      ownerCatalog: seller.voterId,
+      timesSold: new BN(0),
+    }, quantity)
+    .accounts({
+      physProduct: product.publicKey,
+      vendorListings: listings(seller.voterId),
+      sellerAccount: seller.account,
+      sellerWallet: seller.wallet.publicKey,
+    })
+    .signers([product, seller.wallet])
+    .rpc();
+  return product.publicKey;
+}
+
+/// jurors vote the dispute into a ruling for `favor`
+export async function resolveDispute(tx: PublicKey, favor: MarketUser, jurors: MarketUser[]) {
+  const dispute = disputeAccount(tx);
+  for (const juror of jurors) {
+    await disputeProgram.methods
+      .voteDispute(favor.voterId)
+      .accounts({
+        disputeAccount: dispute,
+        voterMarketAccount: juror.account,
+        wallet: juror.wallet.publicKey,
+      })
+      .signers([juror.wallet])
+      .rpc();
+  }
+}
+
+/////////////////////////////////
+/// HELPERS
+
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+export async function lamports(address: PublicKey): Promise<number> {
+  return provider.connection.getBalance(address, "confirmed");
+}
+
+/// awaits the call and checks it failed with the named program or constraint error
+export async function expectError(call: Promise<unknown>, code: string) {
+  let thrown: any = undefined;
+  try {
+    await call;
+  } catch (e) {
+    thrown = e;
+  }
+  if (thrown === undefined) {
+    throw new Error(`expected ${code}, the call succeeded`);
+  }
+  const got = thrown.error?.errorCode?.code ?? thrown.toString();
+  if (!got.includes(code)) {
+    throw new Error(`expected ${code}, got ${got}`);
+  }
+}
@@ -1,16 +1,634 @@
-import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
-import { OrbitPhysicalMarket } from "../target/types/orbit_physical_market";
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  program,
+  accountsProgram,
+  productProgram,
+  transactionProgram,
+  disputeProgram,
+  multisig,
+  marketConfig,
+  feeConfig,
+  physicalAuth,
+  physicalTransaction,
+  escrow,
+  sellerPayout,
+  sellerEncryptionKey,
+  listings,
+  disputeBond,
+  appealBond,
+  disputeResolution,
+  disputeAccount,
+  MarketUser,
+  createUser,
+  listProduct,
+  resolveDispute,
+  sleep,
+  lamports,
+  expectError,
+} from "./fixtures";
+
+const PRICE = new BN(LAMPORTS_PER_SOL);
+const HOUR = new BN(3600);
+const SECOND = new BN(1);
+
+type Order = {
+  tx: PublicKey,
+  escrow: PublicKey,
+  product: PublicKey,
+  cart: PublicKey[],
+};
 
 describe("orbit-physical-market", () => {
-  // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.Provider.env());
+  let seller: MarketUser;
+  let buyer: MarketUser;
+  let jurors: MarketUser[];
+  let product: PublicKey;
+  let cartProduct: PublicKey;
+  let sellerIndex = 0;
+  let buyerIndex = 0;
+
+  /////////////////////////////////
+  /// CONFIG
+
+  const configAccounts = () => ({
+    marketConfig: marketConfig(),
+    multisigSigner: multisig,
+  });
+
+  const setInspectionWindow = (window: BN) =>
+    program.methods.setInspectionWindow(window).accounts(configAccounts()).rpc();
+  const setDefaultHandlingTime = (handling: BN) =>
+    program.methods.setDefaultHandlingTime(handling).accounts(configAccounts()).rpc();
+  const setResolutionWindow = (window: BN) =>
+    program.methods.setDisputeWindows(new BN(0), window, 5000).accounts(configAccounts()).rpc();
+  const setAppealWindow = (window: BN) =>
+    program.methods.setAppeals(window, 3).accounts(configAccounts()).rpc();
+
+  before(async () => {
+    seller = await createUser();
+    buyer = await createUser();
+    jurors = [await createUser(), await createUser(), await createUser()];
+    product = await listProduct(seller, PRICE, 100);
+    cartProduct = await listProduct(seller, PRICE, 100);
+
+    await program.methods
+      .initMarketConfig(HOUR, HOUR)
+      .accounts({
+        ...configAccounts(),
+        payer: multisig,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .initFeeConfig({ platformFeeBps: 500, reflinkShareBps: 100, disputeDiscountBps: 500 })
+      .accounts({
+        feeConfig: feeConfig(),
+        multisigSigner: multisig,
+        payer: multisig,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods.setDisputeBond(500, new BN(0)).accounts(configAccounts()).rpc();
+    await setResolutionWindow(HOUR);
+    await setAppealWindow(HOUR);
+  });
+
+  /////////////////////////////////
+  /// ORDER LIFECYCLE
+
+  // every account the sol escrow instructions take. anchor ignores the ones an instruction doesnt use.
+  // the provider wallet pays the signature fees, so buyer and seller balances only move by payouts
+  const escrowAccounts = (order: Order) => ({
+    physicalTransaction: order.tx,
+    physProduct: order.product,
+    escrowAccount: order.escrow,
+    buyerAccount: buyer.account,
+    buyerTransactionsLog: buyer.buyerLog,
+    buyerWallet: buyer.wallet.publicKey,
+    sellerAccount: seller.account,
+    sellerListings: listings(seller.voterId),
+    sellerTransactionsLog: seller.sellerLog,
+    sellerPayout: sellerPayout(seller.voterId, SystemProgram.programId),
+    sellerWallet: seller.wallet.publicKey,
+    coupon: null,
+    multisigWallet: multisig,
+    physicalAuth: physicalAuth(),
+    feeConfig: feeConfig(),
+    marketConfig: marketConfig(),
+    physicalProgram: program.programId,
+    marketAccountProgram: accountsProgram.programId,
+    marketAccountsProgram: accountsProgram.programId,
+    productProgram: productProgram.programId,
+    transactionProgram: transactionProgram.programId,
+  });
+
+  async function openOrder(cart: PublicKey[] = []): Promise<Order> {
+    const tx = physicalTransaction(seller.sellerLog, sellerIndex);
+    const order = { tx, escrow: escrow(tx, buyer.buyerLog), product, cart };
+    const accounts = {
+      physicalTransaction: tx,
+      escrowAccount: order.escrow,
+      physProduct: product,
+      buyerTransactionsLog: buyer.buyerLog,
+      buyerMarketAccount: buyer.account,
+      buyerWallet: buyer.wallet.publicKey,
+      sellerMarketAccount: seller.account,
+      sellerListings: listings(seller.voterId),
+      sellerTransactionsLog: seller.sellerLog,
+      physicalAuth: physicalAuth(),
+      feeConfig: feeConfig(),
+      coupon: null,
+      sellerEncryptionKey: sellerEncryptionKey(seller.voterId),
+      physicalProgram: program.programId,
+      transactionProgram: transactionProgram.programId,
+      marketAccountProgram: accountsProgram.programId,
+      productProgram: productProgram.programId,
+      systemProgram: SystemProgram.programId,
+    };
+
+    if (cart.length == 0) {
+      await program.methods
+        .openTransactionSol(sellerIndex, buyerIndex, PRICE, false, 1)
+        .accounts(accounts)
+        .signers([buyer.wallet])
+        .rpc();
+    } else {
+      await program.methods
+        .openCartTransactionSol(sellerIndex, buyerIndex, PRICE.muln(cart.length + 1), false, Array(cart.length + 1).fill(1))
+        .accounts(accounts)
+        .remainingAccounts(cart.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .signers([buyer.wallet])
+        .rpc();
+    }
+    sellerIndex += 1;
+    buyerIndex += 1;
+    return order;
+  }
+
+  async function fundOrder(cart: PublicKey[] = []): Promise<Order> {
+    const order = await openOrder(cart);
+    await program.methods
+      .sellerAcceptTransaction(null, null, null)
+      .accounts({
+        physicalTransaction: order.tx,
+        sellerMarketAccount: seller.account,
+        wallet: seller.wallet.publicKey,
+        marketConfig: marketConfig(),
+      })
+      .signers([seller.wallet])
+      .rpc();
+    await program.methods
+      .fundEscrowSol()
+      .accounts({
+        physicalTransaction: order.tx,
+        escrowAccount: order.escrow,
+        buyerTransactionsLog: buyer.buyerLog,
+        buyerMarketAccount: buyer.account,
+        buyerWallet: buyer.wallet.publicKey,
+        marketConfig: marketConfig(),
+      })
+      .signers([buyer.wallet])
+      .rpc();
+    return order;
+  }
+
+  async function shipOrder(cart: PublicKey[] = []): Promise<Order> {
+    const order = await fundOrder(cart);
+    await program.methods
+      .updateShipping({ usps: {} }, Array(32).fill(0))
+      .accounts({
+        physicalTransaction: order.tx,
+        sellerMarketAccount: seller.account,
+        sellerTransactions: seller.sellerLog,
+        wallet: seller.wallet.publicKey,
+      })
+      .signers([seller.wallet])
+      .rpc();
+    return order;
+  }
+
+  async function deliverOrder(): Promise<Order> {
+    const order = await shipOrder();
+    await program.methods
+      .confirmDelivery()
+      .accounts({
+        physicalTransaction: order.tx,
+        buyerAccount: buyer.account,
+        buyerTransactions: buyer.buyerLog,
+        buyerWallet: buyer.wallet.publicKey,
+      })
+      .signers([buyer.wallet])
+      .rpc();
+    return order;
+  }
+
+  const fetchTx = (order: Order) => program.account.physicalTransaction.fetch(order.tx);
+
+  /////////////////////////////////
+  /// AUTO RELEASE
+
+  describe("auto release", () => {
+    it("rejects while the inspection window is open", async () => {
+      const order = await shipOrder();
+      await expectError(
+        program.methods.autoReleaseSol().accounts(escrowAccounts(order)).rpc(),
+        "InspectionWindowOpen"
+      );
+    });
+
+    it("pays the seller less the platform fee once the window passed", async () => {
+      const order = await shipOrder();
+      await setInspectionWindow(SECOND);
+      await sleep(2000);
+
+      const sellerBefore = await lamports(seller.wallet.publicKey);
+      await program.methods.autoReleaseSol().accounts(escrowAccounts(order)).rpc();
+      await setInspectionWindow(HOUR);
+
+      expect(await lamports(seller.wallet.publicKey) - sellerBefore).to.equal(PRICE.toNumber() * 0.95);
+      expect(await lamports(order.escrow)).to.equal(0);
+      expect((await fetchTx(order)).metadata.transactionState).to.deep.equal({ closed: {} });
+    });
+  });
+
+  /////////////////////////////////
+  /// SHIP DEADLINE
+
+  describe("unshipped refunds", () => {
+    it("rejects before the ship deadline", async () => {
+      const order = await fundOrder();
+      await expectError(
+        program.methods.reclaimUnshippedSol().accounts(escrowAccounts(order)).signers([buyer.wallet]).rpc(),
+        "ShipDeadlineNotPassed"
+      );
+    });
+
+    it("refunds the buyer once the seller missed the deadline", async () => {
+      await setDefaultHandlingTime(SECOND);
+      const order = await fundOrder();
+      await setDefaultHandlingTime(HOUR);
+      await sleep(2000);
+
+      const escrowed = await lamports(order.escrow);
+      const buyerBefore = await lamports(buyer.wallet.publicKey);
+      await program.methods.reclaimUnshippedSol().accounts(escrowAccounts(order)).signers([buyer.wallet]).rpc();
+
+      expect(await lamports(buyer.wallet.publicKey) - buyerBefore).to.equal(escrowed);
+      expect((await fetchTx(order)).metadata.transactionState).to.deep.equal({ closed: {} });
+    });
+  });
+
+  /////////////////////////////////
+  /// PARTIAL REFUNDS
+
+  describe("partial refunds", () => {
+    const propose = (order: Order, proposer: MarketUser, amount: BN) =>
+      program.methods
+        .proposePartialRefund(amount)
+        .accounts({
+          physicalTransaction: order.tx,
+          proposerAccount: proposer.account,
+          wallet: proposer.wallet.publicKey,
+        })
+        .signers([proposer.wallet])
+        .rpc();
+
+    it("rejects a refund larger than the escrow", async () => {
+      const order = await shipOrder();
+      await expectError(propose(order, seller, PRICE.addn(1)), "InvalidRefundAmount");
+    });
+
+    it("rejects accepting a different amount than proposed", async () => {
+      const order = await shipOrder();
+      await propose(order, seller, PRICE.divn(2));
+      await expectError(
+        program.methods
+          .acceptPartialRefundSol(PRICE.divn(4))
+          .accounts({ ...escrowAccounts(order), wallet: buyer.wallet.publicKey })
+          .signers([buyer.wallet])
+          .rpc(),
+        "RefundProposalChanged"
+      );
+    });
+
+    it("splits escrow when the other side accepts", async () => {
+      const order = await shipOrder();
+      const refund = PRICE.divn(2);
+      await propose(order, seller, refund);
+
+      const buyerBefore = await lamports(buyer.wallet.publicKey);
+      const sellerBefore = await lamports(seller.wallet.publicKey);
+      await program.methods
+        .acceptPartialRefundSol(refund)
+        .accounts({ ...escrowAccounts(order), wallet: buyer.wallet.publicKey })
+        .signers([buyer.wallet])
+        .rpc();
+
+      expect(await lamports(buyer.wallet.publicKey) - buyerBefore).to.equal(refund.toNumber());
+      expect(await lamports(seller.wallet.publicKey) - sellerBefore).to.equal(refund.toNumber() * 0.95);
+      expect(await lamports(order.escrow)).to.equal(0);
+    });
+  });
+
+  /////////////////////////////////
+  /// RETURNS
+
+  describe("returns", () => {
+    const returnStep = (order: Order, method: "requestReturn" | "authorizeReturn") => {
+      const call = method == "requestReturn" ?
+        program.methods.requestReturn({ defective: {} }).accounts({
+          physicalTransaction: order.tx,
+          buyerAccount: buyer.account,
+          buyerTransactions: buyer.buyerLog,
+          buyerWallet: buyer.wallet.publicKey,
+        }).signers([buyer.wallet]) :
+        program.methods.authorizeReturn().accounts({
+          physicalTransaction: order.tx,
+          sellerMarketAccount: seller.account,
+          wallet: seller.wallet.publicKey,
+        }).signers([seller.wallet]);
+      return call.rpc();
+    };
+
+    const shipReturn = (order: Order) =>
+      program.methods
+        .updateReturnShipping({
+          scheme: 1,
+          ephemeralKey: Array(32).fill(1),
+          nonce: Array(24).fill(2),
+          ciphertext: Buffer.alloc(48, 3),
+        })
+        .accounts({
+          physicalTransaction: order.tx,
+          buyerAccount: buyer.account,
+          buyerWallet: buyer.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([buyer.wallet])
+        .rpc();
+
+    const confirmReturn = (order: Order) =>
+      program.methods.confirmReturnSol().accounts(escrowAccounts(order)).signers([seller.wallet]).rpc();
+
+    it("rejects a second return request", async () => {
+      const order = await deliverOrder();
+      await returnStep(order, "requestReturn");
+      await expectError(returnStep(order, "requestReturn"), "InvalidReturnState");
+    });
+
+    it("rejects confirming a return that wasnt shipped back", async () => {
+      const order = await deliverOrder();
+      await returnStep(order, "requestReturn");
+      await returnStep(order, "authorizeReturn");
+      await expectError(confirmReturn(order), "ConstraintRaw");
+    });
+
+    it("refunds the buyer once the seller receives the return", async () => {
+      const order = await deliverOrder();
+      await returnStep(order, "requestReturn");
+      await returnStep(order, "authorizeReturn");
+      await shipReturn(order);
+
+      const escrowed = await lamports(order.escrow);
+      const buyerBefore = await lamports(buyer.wallet.publicKey);
+      await confirmReturn(order);
+
+      expect(await lamports(buyer.wallet.publicKey) - buyerBefore).to.equal(escrowed);
+      const tx = await fetchTx(order);
+      expect(tx.returnState).to.deep.equal({ received: {} });
+      expect(tx.metadata.transactionState).to.deep.equal({ closed: {} });
+    });
+  });
+
+  /////////////////////////////////
+  /// DISPUTES
+
+  describe("disputes", () => {
+    const postBond = (order: Order, opener: MarketUser) =>
+      program.methods
+        .postDisputeBondSol()
+        .accounts({
+          physicalTransaction: order.tx,
+          disputeBond: disputeBond(order.tx, opener.voterId),
+          openerAccount: opener.account,
+          openerWallet: opener.wallet.publicKey,
+          marketConfig: marketConfig(),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([opener.wallet])
+        .rpc();
+
+    async function disputeOrder(opener: MarketUser = buyer): Promise<Order> {
+      const order = await shipOrder();
+      await postBond(order, opener);
+      await program.methods
+        .openDispute(3)
+        .accounts({
+          physicalTransaction: order.tx,
+          newDispute: disputeAccount(order.tx),
+          openerWallet: opener.wallet.publicKey,
+          disputeBond: disputeBond(order.tx, opener.voterId),
+          marketConfig: marketConfig(),
+          buyer: buyer.account,
+          seller: seller.account,
+          physicalAuth: physicalAuth(),
+          disputeProgram: disputeProgram.programId,
+          physicalProgram: program.programId,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([opener.wallet])
+        .rpc();
+      return order;
+    }
+
+    const disputeAccounts = (order: Order, favor: MarketUser, opener: MarketUser = buyer) => ({
+      ...escrowAccounts(order),
+      physDispute: disputeAccount(order.tx),
+      favorMarketAccount: favor.account,
+      disputeResolution: disputeResolution(order.tx),
+      funder: opener.wallet.publicKey,
+      disputeProgram: disputeProgram.programId,
+    });
+
+    const settleBond = (order: Order, opener: MarketUser, counterparty: MarketUser) =>
+      program.methods
+        .settleDisputeBondSol()
+        .accounts({
+          physicalTransaction: order.tx,
+          disputeBond: disputeBond(order.tx, opener.voterId),
+          openerWallet: opener.wallet.publicKey,
+          counterpartyAccount: counterparty.account,
+          counterpartyWallet: counterparty.wallet.publicKey,
+        })
+        .rpc();
+
+    async function rule(order: Order, favor: MarketUser, buyerBps?: number) {
+      await resolveDispute(order.tx, favor, jurors);
+      if (buyerBps !== undefined) {
+        await program.methods
+          .setDisputeResolution(buyerBps)
+          .accounts({
+            physicalTransaction: order.tx,
+            disputeResolution: disputeResolution(order.tx),
+            multisigSigner: multisig,
+            payer: multisig,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+      await program.methods
+        .recordDisputeRuling()
+        .accounts({
+          physicalTransaction: order.tx,
+          physDispute: disputeAccount(order.tx),
+          favorMarketAccount: favor.account,
+          favorWallet: favor.wallet.publicKey,
+        })
+        .signers([favor.wallet])
+        .rpc();
+    }
+
+    const appeal = (order: Order, appellant: MarketUser, resolutionPayer: PublicKey | null) =>
+      program.methods
+        .appealDisputeSol()
+        .accounts({
+          physicalTransaction: order.tx,
+          physDispute: disputeAccount(order.tx),
+          disputeResolution: disputeResolution(order.tx),
+          resolutionPayer,
+          funder: buyer.wallet.publicKey,
+          appealBond: appealBond(order.tx),
+          appellantAccount: appellant.account,
+          appellantWallet: appellant.wallet.publicKey,
+          buyer: buyer.account,
+          seller: seller.account,
+          marketConfig: marketConfig(),
+          physicalAuth: physicalAuth(),
+          disputeProgram: disputeProgram.programId,
+          physicalProgram: program.programId,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([appellant.wallet])
+        .rpc();
+
+    it("rejects settling a bond before the dispute closed", async () => {
+      const order = await disputeOrder();
+      await expectError(settleBond(order, buyer, seller), "ConstraintRaw");
+    });
+
+    it("rejects default judgment inside the resolution window", async () => {
+      const order = await disputeOrder();
+      await expectError(
+        program.methods.defaultJudgmentSol().accounts(disputeAccounts(order, buyer)).rpc(),
+        "DisputeNotResolved"
+      );
+    });
+
+    it("splits escrow by default judgment and forfeits the losing bond", async () => {
+      const order = await disputeOrder();
+      const bond = await program.account.disputeBond.fetch(disputeBond(order.tx, buyer.voterId));
+      await setResolutionWindow(SECOND);
+      await sleep(2000);
+
+      const escrowed = await lamports(order.escrow);
+      const buyerBefore = await lamports(buyer.wallet.publicKey);
+      const sellerBefore = await lamports(seller.wallet.publicKey);
+      await program.methods.defaultJudgmentSol().accounts(disputeAccounts(order, buyer)).rpc();
+      await setResolutionWindow(HOUR);
+
+      // default split is 5000 bps, the fee only comes off the sellers half
+      expect(await lamports(buyer.wallet.publicKey) - buyerBefore).to.equal(escrowed / 2);
+      expect(await lamports(seller.wallet.publicKey) - sellerBefore).to.equal(escrowed / 2 * 0.95);
+      expect((await fetchTx(order)).disputeBuyerBps).to.equal(5000);
+
+      // an even split is a loss for the opener, the bond goes to the seller
+      const counterpartyBefore = await lamports(seller.wallet.publicKey);
+      await settleBond(order, buyer, seller);
+      expect(await lamports(seller.wallet.publicKey) - counterpartyBefore).to.equal(bond.amount.toNumber());
+    });
+
+    it("rejects closing a ruling still open to appeal", async () => {
+      const order = await disputeOrder();
+      await rule(order, seller);
+      await expectError(
+        program.methods.closeDisputeSol().accounts(disputeAccounts(order, seller)).rpc(),
+        "AppealWindowOpen"
+      );
+    });
+
+    it("pays out the multisig split once the appeal window passed", async () => {
+      const order = await disputeOrder();
+      await setAppealWindow(SECOND);
+      await rule(order, seller, 2000);
+      await sleep(2000);
+
+      const escrowed = await lamports(order.escrow);
+      const buyerBefore = await lamports(buyer.wallet.publicKey);
+      await program.methods.closeDisputeSol().accounts(disputeAccounts(order, seller)).rpc();
+      await setAppealWindow(HOUR);
+
+      expect(await lamports(buyer.wallet.publicKey) - buyerBefore).to.equal(escrowed * 0.2);
+      expect((await fetchTx(order)).disputeBuyerBps).to.equal(2000);
+    });
+
+    it("rejects an appeal before a ruling was recorded", async () => {
+      const order = await disputeOrder();
+      await expectError(appeal(order, buyer, null), "ConstraintRaw");
+    });
+
+    it("escalates an appeal and drops the appealed split", async () => {
+      const order = await disputeOrder();
+      await rule(order, seller, 2000);
+
+      await appeal(order, buyer, multisig);
+
+      const tx = await fetchTx(order);
+      expect(tx.appealed).to.equal(true);
+      expect((await program.account.disputeBond.fetch(appealBond(order.tx))).used).to.equal(true);
+      expect(await program.provider.connection.getAccountInfo(disputeResolution(order.tx))).to.equal(null);
+    });
+  });
+
+  /////////////////////////////////
+  /// CART SETTLEMENT
+
+  describe("cart settlement", () => {
+    const settleCart = (order: Order, products: PublicKey[]) =>
+      program.methods
+        .settleCartItems()
+        .accounts({
+          physicalTransaction: order.tx,
+          sellerAccount: seller.account,
+          sellerListings: listings(seller.voterId),
+          physicalAuth: physicalAuth(),
+          physicalProgram: program.programId,
+          productProgram: productProgram.programId,
+        })
+        .remainingAccounts(products.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .rpc();
+
+    async function releasedCart(): Promise<Order> {
+      const order = await shipOrder([cartProduct]);
+      await setInspectionWindow(SECOND);
+      await sleep(2000);
+      await program.methods.autoReleaseSol().accounts(escrowAccounts(order)).rpc();
+      await setInspectionWindow(HOUR);
+      return order;
+    }
+
+    it("rejects settling without the cart products", async () => {
+      const order = await releasedCart();
+      await expectError(settleCart(order, []), "InvalidCartItems");
+    });
 
-  const program = anchor.workspace.OrbitPhysicalMarket as Program<OrbitPhysicalMarket>;
+    it("marks the extra cart lines sold after release", async () => {
+      const order = await releasedCart();
+      expect((await fetchTx(order)).cartSettlement).to.deep.equal({ sold: {} });
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.rpc.initialize({});
-    console.log("Your transaction signature", tx);
+      await settleCart(order, order.cart);
+      expect((await fetchTx(order)).cartSettlement).to.deep.equal({ none: {} });
+    });
   });
 });